@@ -0,0 +1,34 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use crate::EhResult;
+
+/// Reads a date as the site prints it, always in UTC:
+/// `2023-02-07 07:33` in the lists and on the detail page, `24 September 2020, 09:55` in the comments.
+pub fn parse_datetime(str: &str) -> EhResult<DateTime<Utc>> {
+    let str = str.trim();
+
+    let mut result = NaiveDateTime::parse_from_str(str, FORMAT_LIST[0]);
+    for format in &FORMAT_LIST[1..] {
+        if result.is_ok() {
+            break;
+        }
+        result = NaiveDateTime::parse_from_str(str, format);
+    }
+
+    Ok(Utc.from_utc_datetime(&result?))
+}
+
+const FORMAT_LIST: [&str; 4] = ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%d %B %Y, %H:%M", "%d %B %Y, %H:%M:%S"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_datetime_test() {
+        let expected = Utc.with_ymd_and_hms(2020, 9, 24, 9, 55, 0).unwrap();
+        assert_eq!(parse_datetime("2020-09-24 09:55").unwrap(), expected);
+        assert_eq!(parse_datetime(" 24 September 2020, 09:55 ").unwrap(), expected);
+        assert_eq!(parse_datetime("2020-09-24 09:55:12").unwrap().timestamp(), expected.timestamp() + 12);
+        assert!(parse_datetime("yesterday").is_err());
+    }
+}
@@ -1,7 +1,4 @@
-#![allow(dead_code, non_upper_case_globals)]
-
-use std::sync::Mutex;
-use once_cell::sync::Lazy;
+#![allow(dead_code)]
 
 /**
  * The Cookie key of uconfig
@@ -19,6 +16,10 @@ pub const KEY_CONTENT_WARNING: &str = "nw";
  * load images through the Hentai@Home Network
  */
 pub const LOAD_FROM_HAH_YES: &str = "y";
+/**
+ * load images through the Hentai@Home clients on the default port only
+ */
+pub const LOAD_FROM_HAH_DEFAULT_PORT: &str = "p";
 /**
  * do not load images through the Hentai@Home Network
  */
@@ -294,240 +295,156 @@ pub const CONTENT_WARNING_NOT_SHOW: &str = "1";
 /**
  * Default gallery_list title
  */
-const GALLERY_TITLE_DEFAULT: &str = "r";
+pub const GALLERY_TITLE_DEFAULT: &str = "r";
+/**
+ * Japanese gallery_list title
+ */
+pub const GALLERY_TITLE_JAPANESE: &str = "j";
 /**
  * Show popular
  */
-const POPULAR_YES: &str = "y";
+pub const POPULAR_YES: &str = "y";
+/**
+ * Do not show popular
+ */
+pub const POPULAR_NO: &str = "n";
 /**
  * Sort favorites by last gallery_list update time
  */
-const FAVORITES_SORT_FAVORITED_TIME: &str = "f";
+pub const FAVORITES_SORT_GALLERY_UPDATE_TIME: &str = "p";
 /**
- * Load images through the Hentai@Home Network<br/>
- * key: {@link #KEY_LOAD_FROM_HAH}<br/>
- * value: {@link #LOAD_FROM_HAH_YES}, {@link #LOAD_FROM_HAH_NO}
+ * Sort favorites by favorited time
  */
-pub static mut loadFromHAH: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(LOAD_FROM_HAH_YES));
-
+pub const FAVORITES_SORT_FAVORITED_TIME: &str = "f";
 /**
- * Image Size<br/>
- * key: {@link #KEY_IMAGE_SIZE}<br/>
- * value: {@link #IMAGE_SIZE_AUTO}, {@link #IMAGE_SIZE_780X}, {@link #IMAGE_SIZE_980X},
- * {@link #IMAGE_SIZE_1280X}, {@link #IMAGE_SIZE_1600X}, {@link #IMAGE_SIZE_2400X}
+ * Minimal View on the front and search pages
  */
-pub static imageSize: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(IMAGE_SIZE_AUTO));
-
+pub const LAYOUT_MODE_MINIMAL: &str = "m";
 /**
- * Scale width<br/>
- * key: {@link #KEY_SCALE_WIDTH}<br/>
- * value: 0 for no limit
+ * Minimal+ View on the front and search pages
  */
-pub static scaleWidth: Lazy<Mutex<u32>> = Lazy::new(|| Mutex::new(0));
-
+pub const LAYOUT_MODE_MINIMAL_PLUS: &str = "p";
 /**
- * Scale height<br/>
- * key: {@link #KEY_SCALE_HEIGHT}<br/>
- * value: 0 for no limit
+ * Extended View on the front and search pages
  */
-pub static scaleHeight: Lazy<Mutex<u32>> = Lazy::new(|| Mutex::new(0));
-
+pub const LAYOUT_MODE_EXTENDED: &str = "e";
 /**
- * Gallery title<br/>
- * key: {@link #KEY_GALLERY_TITLE}<br/>
- * value: {@link #GALLERY_TITLE_DEFAULT}, {@link #GALLERY_TITLE_JAPANESE}
+ * Separates the keys and values in the uconfig cookie
  */
-pub static galleryTitle: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(GALLERY_TITLE_DEFAULT));
-
+pub const UCONFIG_KEY_VALUE_SEPARATOR: char = '_';
 /**
- * The default behavior for downloading an archiver<br/>
- * key: {@link #KEY_ARCHIVER_DOWNLOAD}<br/>
- * value: {@link #ARCHIVER_DOWNLOAD_MAMS}, {@link #ARCHIVER_DOWNLOAD_AAMS},
- * {@link #ARCHIVER_DOWNLOAD_MAAS}, {@link #ARCHIVER_DOWNLOAD_AAAS}
+ * Separates the settings in the uconfig cookie
  */
-pub static archiverDownload: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(ARCHIVER_DOWNLOAD_MAMS));
-
+pub const UCONFIG_SETTING_SEPARATOR: char = '-';
 /**
- * Display mode used on the front and search pages<br/>
- * false for list, true for thumb<br/>
- * key: {@link #KEY_LAYOUT_MODE}<br/>
- * value: {@link #LAYOUT_MODE_LIST}, {@link #LAYOUT_MODE_THUMB}
+ * Separates the excluded languages in the uconfig cookie
  */
-pub static layoutMode: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(LAYOUT_MODE_LIST));
-
+pub const UCONFIG_LANGUAGE_SEPARATOR: char = 'x';
 /**
- * Show popular or not<br/>
- * key: {@link #KEY_POPULAR}<br/>
- * value: {@link #POPULAR_YES}, {@link #POPULAR_NO}
+ * Load images through the Hentai@Home Network
  */
-pub static popular: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(POPULAR_YES));
-
+pub const KEY_LOAD_FROM_HAH: &str = "uh";
 /**
- * Default categories on the front page<br/>
- * key: {@link #KEY_DEFAULT_CATEGORIES}<br/>
- * value: the value of categories, for multiple use & operation,
- * 0 for none
+ * Image Size
  */
-pub static defaultCategories: Lazy<Mutex<u32>> = Lazy::new(|| Mutex::new(0));
-
+pub const KEY_IMAGE_SIZE: &str = "xr";
 /**
- * <br/>
- * key: {@link #KEY_FAVORITES_SORT}<br/>
- * value: {@link #FAVORITES_SORT_GALLERY_UPDATE_TIME}, {@link #FAVORITES_SORT_FAVORITED_TIME}
+ * Scale width
  */
-pub static favoritesSort: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(FAVORITES_SORT_FAVORITED_TIME));
-
+pub const KEY_SCALE_WIDTH: &str = "rx";
 /**
- * Certain namespaces excluded from a default tag search<br/>
- * key: {@link #KEY_EXCLUDED_NAMESPACES}<br/>
- * value: the value of namespaces, for multiple use & operation,
- * 0 for none
+ * Scale height
  */
-pub static excludedNamespaces: Lazy<Mutex<u32>> = Lazy::new(|| Mutex::new(0));
-
+pub const KEY_SCALE_HEIGHT: &str = "ry";
 /**
- * Certain languages excluded from list and searches<br/>
- * key: {@link #KEY_EXCLUDED_LANGUAGES}<br/>
- * value: {@link #JAPANESE_TRANSLATED}, {@link #JAPANESE_REWRITE} ...
- * For multiple languages, use <code>x<code/> to combine them, like 1x1024x2048
+ * Gallery title
  */
-pub static excludedLanguages: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(""));
-
+pub const KEY_GALLERY_TITLE: &str = "tl";
 /**
- * How many results would you like per page for the index/search page
- * and torrent search pages<br/>
- * key: {@link #KEY_RESULT_COUNT}<br/>
- * value: {@link #RESULT_COUNT_25}, {@link #RESULT_COUNT_50},
- * {@link #RESULT_COUNT_100}, {@link #RESULT_COUNT_200}<br/>
- * Require <code>Hath Perk:Paging Enlargement</code>
+ * The default behavior for downloading an archiver
  */
-pub static resultCount: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(RESULT_COUNT_25));
-
+pub const KEY_ARCHIVER_DOWNLOAD: &str = "ar";
 /**
- * mouse-over thumb<br/>
- * key: {@link #KEY_MOUSE_OVER}<br/>
- * value: {@link #MOUSE_OVER_YES}, {@link #MOUSE_OVER_NO}
+ * Display mode used on the front and search pages
  */
-pub static mouseOver: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(MOUSE_OVER_YES));
-
+pub const KEY_LAYOUT_MODE: &str = "dm";
 /**
- * Default preview mode<br/>
- * key: {@link #KEY_PREVIEW_SIZE}<br/>
- * value: {@link #PREVIEW_SIZE_NORMAL}, {@link #PREVIEW_SIZE_LARGE}
+ * Show popular or not
  */
-pub static previewSize: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(PREVIEW_SIZE_LARGE));
-
+pub const KEY_POPULAR: &str = "prn";
 /**
- * Preview row<br/>
- * key: {@link #KEY_PREVIEW_ROW}<br/>
- * value: {@link #PREVIEW_ROW_4}, {@link #PREVIEW_ROW_10},
- * {@link #PREVIEW_ROW_20}, {@link #PREVIEW_ROW_40}
+ * Default categories on the front page
  */
-pub static previewRow: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(PREVIEW_ROW_4));
-
+pub const KEY_DEFAULT_CATEGORIES: &str = "cats";
 /**
- * Sort order for gallery_list comments<br/>
- * key: {@link #KEY_COMMENTS_SORT}<br/>
- * value: {@link #COMMENTS_SORT_OLDEST_FIRST}, {@link #COMMENTS_SORT_RECENT_FIRST},
- * {@link #COMMENTS_SORT_HIGHEST_SCORE_FIRST}
+ * Sort order of the favorites
  */
-pub static commentSort: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(COMMENTS_SORT_OLDEST_FIRST));
-
+pub const KEY_FAVORITES_SORT: &str = "fs";
 /**
- * Show gallery_list comment votes mode<br/>
- * key: {@link #KEY_COMMENTS_VOTES}<br/>
- * value: {@link #COMMENTS_VOTES_POP}, {@link #COMMENTS_VOTES_ALWAYS}
+ * Certain namespaces excluded from a default tag search
  */
-pub static commentVotes: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(COMMENTS_VOTES_POP));
-
-
+pub const KEY_EXCLUDED_NAMESPACES: &str = "xns";
 /**
- * Sort order for gallery_list tags<br/>
- * key: {@link #KEY_TAGS_SORT}<br/>
- * value: {@link #TAGS_SORT_ALPHABETICAL}, {@link #TAGS_SORT_POWER}
+ * Certain languages excluded from list and searches
  */
-pub static tagSort: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(TAGS_SORT_ALPHABETICAL));
-
+pub const KEY_EXCLUDED_LANGUAGES: &str = "xl";
 /**
- * Show gallery_list page numbers<br/>
- * key: {@link #KEY_SHOW_GALLERY_INDEX}<br/>
- * value: {@link #SHOW_GALLERY_INDEX_YES}, {@link #SHOW_GALLERY_INDEX_NO}
+ * How many results would you like per page for the index/search page
  */
-pub static showGalleryIndex: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(SHOW_GALLERY_INDEX_YES));
-
+pub const KEY_RESULT_COUNT: &str = "rc";
 /**
- * The IP of a proxy-enabled Hentai@Home Client
- * to load all images<br/>
- * key: {@link #KEY_HAH_CLIENT_IP_PORT}<br/>
+ * mouse-over thumb
  */
-pub static hahClientIp: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(""));
-
+pub const KEY_MOUSE_OVER: &str = "lt";
 /**
- * The PORT of a proxy-enabled Hentai@Home Client
- * to load all images<br/>
- * key: {@link #KEY_HAH_CLIENT_IP_PORT}<br/>
+ * Default preview mode
  */
-pub static hahClientPort: Lazy<Mutex<i8>> = Lazy::new(|| Mutex::new(-1));
-
+pub const KEY_PREVIEW_SIZE: &str = "ts";
 /**
- * The passkey of a proxy-enabled Hentai@Home Client
- * to load all images<br/>
- * key: {@link #KEY_HAH_CLIENT_PASSKEY}<br/>
+ * Preview row
  */
-pub static hahClientPasskey: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(""));
-
+pub const KEY_PREVIEW_ROW: &str = "tr";
 /**
- * Enable tag flagging
- * key: {@link #KEY_ENABLE_TAG_FLAGGING}<br/>
- * value: {@link #ENABLE_TAG_FLAGGING_YES}, {@link #ENABLE_TAG_FLAGGING_NO}<br/>
- * <code>Bronze Star</code> or <code>Hath Perk: Tag Flagging</code> Required
+ * Sort order for gallery_list comments
  */
-pub static enableTagFlagging: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(ENABLE_TAG_FLAGGING_NO));
-
+pub const KEY_COMMENTS_SORT: &str = "cs";
 /**
- * Always display the original images instead of the resampled versions<br/>
- * key: {@link #KEY_ALWAYS_ORIGINAL}<br/>
- * value: {@link #ALWAYS_ORIGINAL_YES}, {@link #ALWAYS_ORIGINAL_NO}<br/>
- * <code>Silver Star</code> or <code>Hath Perk: Source Nexus</code> Required
+ * Show gallery_list comment votes mode
  */
-pub static alwaysOriginal: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(ALWAYS_ORIGINAL_NO));
-
+pub const KEY_COMMENTS_VOTES: &str = "sc";
 /**
- * Enable the multi-Page Viewer<br/>
- * key: {@link #KEY_MULTI_PAGE}<br/>
- * value: {@link #MULTI_PAGE_YES}, {@link #MULTI_PAGE_NO}<br/>
- * <code>Gold Star</code> or <code>Hath Perk: Multi-Page Viewer</code> Required
+ * Sort order for gallery_list tags
  */
-pub static multiPage: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(MULTI_PAGE_NO));
-
+pub const KEY_TAGS_SORT: &str = "tb";
+/**
+ * Show gallery_list page numbers
+ */
+pub const KEY_SHOW_GALLERY_INDEX: &str = "pn";
 /**
- * Multi-Page Viewer Display Style<br/>
- * key: {@link #KEY_MULTI_PAGE_STYLE}<br/>
- * value: {@link #MULTI_PAGE_STYLE_N}, {@link #MULTI_PAGE_STYLE_C},
- * {@link #MULTI_PAGE_STYLE_Y}<br/>
- * <code>Gold Star</code> or <code>Hath Perk: Multi-Page Viewer</code> Required
+ * The IP:Port of a proxy-enabled Hentai@Home Client
  */
-pub static multiPageStyle: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(MULTI_PAGE_STYLE_N));
-
+pub const KEY_HAH_CLIENT_IP_PORT: &str = "hp";
 /**
- * Multi-Page Viewer Thumbnail Pane<br/>
- * key: {@link #KEY_MULTI_PAGE_THUMB}<br/>
- * value: {@link #MULTI_PAGE_THUMB_SHOW}, {@link #MULTI_PAGE_THUMB_HIDE}<br/>
- * <code>Gold Star</code> or <code>Hath Perk: Multi-Page Viewer</code> Required
+ * The passkey of a proxy-enabled Hentai@Home Client
  */
-pub static multiPageThumb: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(MULTI_PAGE_THUMB_SHOW));
-
+pub const KEY_HAH_CLIENT_PASSKEY: &str = "hk";
 /**
- * Lofi resolution
- * key: {@link #KEY_LOFI_RESOLUTION}<br/>
- * value: {@link #LOFI_RESOLUTION_460X}, {@link #LOFI_RESOLUTION_780X},
- * {@link #LOFI_RESOLUTION_980X}
+ * Enable tag flagging
  */
-pub static lofiResolution: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(LOFI_RESOLUTION_980X));
-
+pub const KEY_ENABLE_TAG_FLAGGING: &str = "tf";
+/**
+ * Always display the original images instead of the resampled versions
+ */
+pub const KEY_ALWAYS_ORIGINAL: &str = "oi";
+/**
+ * Enable the multi-Page Viewer
+ */
+pub const KEY_MULTI_PAGE: &str = "qb";
+/**
+ * Multi-Page Viewer Display Style
+ */
+pub const KEY_MULTI_PAGE_STYLE: &str = "ms";
 /**
- * Show content warning
- * key: {@link #KEY_CONTENT_WARNING}<br/>
- * value: {@link #CONTENT_WARNING_SHOW}, {@link #CONTENT_WARNING_NOT_SHOW}
+ * Multi-Page Viewer Thumbnail Pane
  */
-pub static contentWarning: Lazy<Mutex<&str>> = Lazy::new(|| Mutex::new(CONTENT_WARNING_NOT_SHOW));
+pub const KEY_MULTI_PAGE_THUMB: &str = "mt";
@@ -46,3 +46,75 @@ pub const URL_WATCHED_EX: &str = const_concat!(HOST_EX, "watched");
 
 const URL_PREFIX_THUMB_E: &str = "https://ehgt.org/";
 // const URL_PREFIX_THUMB_EX: &str = "https://exhentai.org/t/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Site {
+    E,
+    Ex,
+}
+
+impl Site {
+    pub fn host(&self) -> &'static str {
+        match self {
+            Site::E => HOST_E,
+            Site::Ex => HOST_EX,
+        }
+    }
+
+    pub fn api(&self) -> &'static str {
+        match self {
+            Site::E => API_E,
+            Site::Ex => API_EX,
+        }
+    }
+
+    pub fn referer(&self) -> &'static str {
+        match self {
+            Site::E => REFERER_E,
+            Site::Ex => REFERER_EX,
+        }
+    }
+
+    pub fn origin(&self) -> &'static str {
+        match self {
+            Site::E => ORIGIN_E,
+            Site::Ex => ORIGIN_EX,
+        }
+    }
+
+    pub fn favorites(&self) -> &'static str {
+        match self {
+            Site::E => URL_FAVORITES_E,
+            Site::Ex => URL_FAVORITES_EX,
+        }
+    }
+
+    pub fn popular(&self) -> &'static str {
+        match self {
+            Site::E => URL_POPULAR_E,
+            Site::Ex => URL_POPULAR_EX,
+        }
+    }
+
+    pub fn watched(&self) -> &'static str {
+        match self {
+            Site::E => URL_WATCHED_E,
+            Site::Ex => URL_WATCHED_EX,
+        }
+    }
+
+    pub fn uconfig(&self) -> &'static str {
+        match self {
+            Site::E => URL_UCONFIG_E,
+            Site::Ex => URL_UCONFIG_EX,
+        }
+    }
+
+    pub fn my_tags(&self) -> &'static str {
+        match self {
+            Site::E => URL_MY_TAGS_E,
+            Site::Ex => URL_MY_TAGS_EX,
+        }
+    }
+}
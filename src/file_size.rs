@@ -0,0 +1,34 @@
+use regex::Regex;
+use crate::{EhResult, REGEX_MATCH_FAILED};
+
+/// `225.5 MB`, the site counts in 1024s whether it writes `MB` or `MiB`.
+pub fn parse_file_size(text: &str) -> EhResult<u64> {
+    let regex = Regex::new(PATTERN_FILE_SIZE).unwrap();
+    let captures = regex.captures(text).ok_or(REGEX_MATCH_FAILED)?;
+    let number = captures[1].replace(',', "").parse::<f64>()?;
+    let exponent = match captures[2].to_ascii_uppercase().as_str() {
+        "B" => 0,
+        "KB" | "KIB" => 1,
+        "MB" | "MIB" => 2,
+        "GB" | "GIB" => 3,
+        "TB" | "TIB" => 4,
+        _ => return Err(REGEX_MATCH_FAILED),
+    };
+
+    Ok((number * 1024f64.powi(exponent)).round() as u64)
+}
+
+pub const PATTERN_FILE_SIZE: &str = r#"([\d,.]+)\s*([KMGT]i?B|B)"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file_size_test() {
+        assert_eq!(parse_file_size("225.5 MB").unwrap(), 236453888);
+        assert_eq!(parse_file_size("1280 x 1810 :: 354.6 KiB").unwrap(), 363110);
+        assert_eq!(parse_file_size("1,023 B").unwrap(), 1023);
+        assert!(parse_file_size("1.5 PB").is_err());
+    }
+}
@@ -1,11 +1,19 @@
 pub mod structures;
+pub mod request;
 
-mod input;
 mod eh_url;
 mod eh_config;
+mod datetime;
+mod file_size;
+mod server_error;
+mod page_kind;
 mod unescape;
+mod url_encoding;
 mod test_helper;
 
+pub use eh_url::Site;
+pub use page_kind::{classify, PageKind};
+
 // result
 
 pub type EhResult<T> = Result<T, ParseError>;
@@ -25,8 +33,19 @@ pub enum ParseError {
     SignInRequired,
     AttributeNotFound,
     DomNotFound,
+    /// A display mode or preview kind the parser does not know, with what was found instead.
+    UnrecognizedLayout(String),
     FromServer(String),
+    /// The image quota is used up, the site sends its 509 image or a page saying so.
+    ImageLimitExceeded,
+    /// The IP address is banned for the duration left.
+    IpBanned(std::time::Duration),
+    GalleryRemoved,
+    /// The key of the page is wrong or no longer valid, the page has to be loaded again from the gallery.
+    KeyExpired,
     Other(BoxDynError),
+    /// Where the wrapped error happened, see [`ParseError::context_chain`].
+    Context(Box<ErrorContext>, Box<ParseError>),
 }
 
 impl std::fmt::Display for ParseError {
@@ -37,9 +56,113 @@ impl std::fmt::Display for ParseError {
             ParseError::SignInRequired => write!(f, "this page requires you to log on"),
             ParseError::AttributeNotFound => write!(f, "attribute cannot be found"),
             ParseError::DomNotFound => write!(f, "dom cannot be found"),
+            ParseError::UnrecognizedLayout(s) => write!(f, "unrecognized layout: {}", s),
             ParseError::FromServer(s) => write!(f, "error from server: {}", s),
+            ParseError::ImageLimitExceeded => write!(f, "the image viewing limit is exceeded"),
+            ParseError::IpBanned(d) => write!(f, "the ip address is banned for {} more seconds", d.as_secs()),
+            ParseError::GalleryRemoved => write!(f, "the gallery has been removed or is unavailable"),
+            ParseError::KeyExpired => write!(f, "the key is missing, incorrect or expired"),
             ParseError::Other(e) => e.fmt(f),
+            ParseError::Context(context, e) => write!(f, "{}: {}", context, e),
+        }
+    }
+}
+
+impl ParseError {
+    /// The contexts from the outermost parser to the innermost one.
+    pub fn context_chain(&self) -> Vec<&ErrorContext> {
+        let mut chain = Vec::new();
+        let mut error = self;
+        while let ParseError::Context(context, source) = error {
+            chain.push(context.as_ref());
+            error = source;
+        }
+
+        chain
+    }
+
+    /// The error without its contexts.
+    pub fn root_cause(&self) -> &ParseError {
+        match self {
+            ParseError::Context(_, source) => source.root_cause(),
+            e => e,
+        }
+    }
+}
+
+/// The field a parser was extracting when it failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorContext {
+    /// The type being parsed, like `GalleryDetail`.
+    pub parser: &'static str,
+    /// Like `posted` or `comment_vec[3]`.
+    pub field: String,
+    /// The selector or the regex used.
+    pub locator_opt: Option<String>,
+    /// The start of the html the field was read from, see [`SNIPPET_MAX_CHARS`].
+    pub snippet_opt: Option<String>,
+}
+
+impl ErrorContext {
+    pub fn new(parser: &'static str, field: &str) -> Self {
+        ErrorContext {
+            parser,
+            field: String::from(field),
+            locator_opt: None,
+            snippet_opt: None,
+        }
+    }
+
+    pub fn locator(mut self, locator: &str) -> Self {
+        self.locator_opt = Some(String::from(locator));
+        self
+    }
+
+    /// Keeps the first [`SNIPPET_MAX_CHARS`] characters of `doc`, with the whitespace collapsed.
+    pub fn snippet(mut self, doc: &str) -> Self {
+        let mut snippet = doc.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if let Some((idx, _)) = snippet.char_indices().nth(SNIPPET_MAX_CHARS) {
+            snippet.truncate(idx);
+            snippet.push('…');
         }
+        self.snippet_opt = Some(snippet);
+        self
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    /// ```text
+    /// GalleryComment.posted `(\d+ \w+ \d+, \d+:\d+)` in `<div class="c3">...</div>`
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.parser, self.field)?;
+        if let Some(locator) = &self.locator_opt {
+            write!(f, " `{}`", locator)?;
+        }
+        if let Some(snippet) = &self.snippet_opt {
+            write!(f, " in `{}`", snippet)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub const SNIPPET_MAX_CHARS: usize = 160;
+
+/// Wraps the error of a result in an [`ErrorContext`].
+pub(crate) trait Context<T> {
+    fn context(self, context: ErrorContext) -> EhResult<T>;
+
+    fn with_context<F: FnOnce() -> ErrorContext>(self, f: F) -> EhResult<T>;
+}
+
+impl<T, E: Into<ParseError>> Context<T> for Result<T, E> {
+    fn context(self, context: ErrorContext) -> EhResult<T> {
+        self.map_err(|e| ParseError::Context(Box::new(context), Box::new(e.into())))
+    }
+
+    fn with_context<F: FnOnce() -> ErrorContext>(self, f: F) -> EhResult<T> {
+        self.map_err(|e| ParseError::Context(Box::new(f()), Box::new(e.into())))
     }
 }
 
@@ -156,4 +279,19 @@ mod tests {
         assert_eq!(GREETING, "Hello, world!");
         assert_eq!(GREETING_TRAILING_COMMA, "Hello, world!");
     }
+
+    #[test]
+    fn error_context_test() {
+        use super::*;
+
+        let context = ErrorContext::new("Thumb", "height").snippet(&"<img  src=\"x\">\n".repeat(40));
+        let snippet = context.snippet_opt.unwrap();
+        assert_eq!(snippet.chars().count(), SNIPPET_MAX_CHARS + 1);
+        assert!(snippet.starts_with("<img src=\"x\"> <img"));
+
+        let error: EhResult<()> = Err(DOM_NOT_FOUND).context(ErrorContext::new("Thumb", "height").locator("img"));
+        let error = error.unwrap_err();
+        assert_eq!(error.to_string(), "Thumb.height `img`: dom cannot be found");
+        assert!(matches!(error.root_cause(), ParseError::DomNotFound));
+    }
 }
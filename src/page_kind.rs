@@ -0,0 +1,173 @@
+use std::time::Duration;
+use regex::Regex;
+use crate::{ParseError, SIGN_IN_REQUIRED, server_error::parse_server_error};
+
+/// What a response of the site is, see [`classify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageKind {
+    GalleryList,
+    GalleryDetail,
+    /// The offensive content warning shown before some details.
+    ContentWarning,
+    GalleryRemoved,
+    SignInRequired,
+    IpBanned(Duration),
+    /// The empty page exhentai sends without the right cookies.
+    SadPanda,
+    ImageLimitExceeded,
+    KeyExpired,
+    /// `/s/`, [`crate::structures::GalleryPage`].
+    GalleryPage,
+    MultiPageViewer,
+    ArchiveForm,
+    TorrentList,
+    Favorites,
+    Forums,
+    /// An answer of `api.php`.
+    Api,
+    /// The `error` of an answer of `api.php`.
+    ApiError(String),
+    /// The message of a `<div class="d">` page not told apart above.
+    ServerError(String),
+    Unknown,
+}
+
+impl PageKind {
+    /// The error a parser returns for this kind of page, `None` for the pages holding data.
+    pub fn error_opt(&self) -> Option<ParseError> {
+        match self {
+            PageKind::ContentWarning => Some(ParseError::FromServer(String::from(CONTENT_WARNING_MESSAGE))),
+            PageKind::GalleryRemoved => Some(ParseError::GalleryRemoved),
+            // exhentai wants the cookies of a signed in user.
+            PageKind::SignInRequired | PageKind::SadPanda => Some(SIGN_IN_REQUIRED),
+            PageKind::IpBanned(duration) => Some(ParseError::IpBanned(*duration)),
+            PageKind::ImageLimitExceeded => Some(ParseError::ImageLimitExceeded),
+            PageKind::KeyExpired => Some(ParseError::KeyExpired),
+            PageKind::ApiError(message) | PageKind::ServerError(message) => Some(ParseError::FromServer(message.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Tells what `doc` is from its markers, the pages holding data first so that a comment
+/// quoting an error does not count.
+pub fn classify(doc: &str) -> PageKind {
+    let trimmed = doc.trim();
+    if trimmed.is_empty() {
+        return PageKind::SadPanda;
+    }
+
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        return classify_api(trimmed);
+    }
+
+    for (marker, kind) in DATA_MARKER_LIST {
+        if doc.contains(marker) {
+            return kind;
+        }
+    }
+
+    if doc.contains(CONTENT_WARNING_STRING) {
+        return PageKind::ContentWarning;
+    }
+
+    if doc.contains(SIGN_IN_REQUIRED_STRING) {
+        return PageKind::SignInRequired;
+    }
+
+    if doc.contains(PINING_STRING) {
+        return PageKind::GalleryRemoved;
+    }
+
+    match parse_server_error(doc) {
+        Some(ParseError::IpBanned(duration)) => return PageKind::IpBanned(duration),
+        Some(ParseError::ImageLimitExceeded) => return PageKind::ImageLimitExceeded,
+        Some(ParseError::GalleryRemoved) => return PageKind::GalleryRemoved,
+        Some(ParseError::KeyExpired) => return PageKind::KeyExpired,
+        _ => {}
+    }
+
+    let regex = Regex::new(PATTERN_ERROR).unwrap();
+    if let Some(captures) = regex.captures(doc) {
+        return PageKind::ServerError(String::from(captures[1].trim()));
+    }
+
+    PageKind::Unknown
+}
+
+/// The error of a parser about to read `doc`, see [`PageKind::error_opt`].
+pub(crate) fn pre_check(doc: &str) -> Result<(), ParseError> {
+    match classify(doc).error_opt() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn classify_api(doc: &str) -> PageKind {
+    let error_opt = serde_json::from_str::<serde_json::Value>(doc).ok()
+        .and_then(|value| value.get("error").and_then(|error| error.as_str()).map(String::from));
+
+    match error_opt {
+        Some(error) => match parse_server_error(&error) {
+            Some(ParseError::IpBanned(duration)) => PageKind::IpBanned(duration),
+            Some(ParseError::ImageLimitExceeded) => PageKind::ImageLimitExceeded,
+            Some(ParseError::KeyExpired) => PageKind::KeyExpired,
+            Some(ParseError::GalleryRemoved) => PageKind::GalleryRemoved,
+            _ => PageKind::ApiError(error),
+        },
+        None => PageKind::Api,
+    }
+}
+
+const CONTENT_WARNING_MESSAGE: &str = "if you choose to ignore this warning, you lose all rights to complain about it in the future.";
+const CONTENT_WARNING_STRING: &str = "(And if you choose to ignore this warning, you lose all rights to complain about it in the future.)";
+const SIGN_IN_REQUIRED_STRING: &str = "This page requires you to log on.";
+const PINING_STRING: &str = "is pining for the fjords.";
+const PATTERN_ERROR: &str = r#"<div class="d">\s*<p>([^<]+)</p>"#;
+
+/// In order, the image page and the viewer before the detail as they link back to it,
+/// the favorites before the list as they hold one.
+const DATA_MARKER_LIST: [(&str, PageKind); 9] = [
+    ("var startkey", PageKind::GalleryPage),
+    ("var mpvkey", PageKind::MultiPageViewer),
+    (r#"id="gdt""#, PageKind::GalleryDetail),
+    (r#"id="hathdl_form""#, PageKind::ArchiveForm),
+    (r#"id="torrentinfo""#, PageKind::TorrentList),
+    (r#"class="fp"#, PageKind::Favorites),
+    (r#"class="itg"#, PageKind::GalleryList),
+    (r#"id="userlinks""#, PageKind::Forums),
+    ("gallerytorrents.php", PageKind::TorrentList),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_test() {
+        assert_eq!(classify(" \n"), PageKind::SadPanda);
+        assert_eq!(classify(r#"{"error":"Key mismatch"}"#), PageKind::KeyExpired);
+        assert_eq!(classify(r#"{"error":"You have exceeded your image viewing limits"}"#), PageKind::ImageLimitExceeded);
+        assert_eq!(classify(r#"{"error":"maomao is moe~"}"#), PageKind::ApiError(String::from("maomao is moe~")));
+        assert_eq!(classify(r#"{"tokenlist":[]}"#), PageKind::Api);
+
+        assert_eq!(classify(r#"<script>var gid=2455981; var startpage=2; var startkey="8a3b1f9e22";</script>"#), PageKind::GalleryPage);
+        assert_eq!(classify(r#"<script>var mpvkey = "0a2b4c6d8e";</script>"#), PageKind::MultiPageViewer);
+        assert_eq!(classify(r#"<div id="gdt" class="gt200"></div><div class="c6">This gallery has been removed or is unavailable.</div>"#), PageKind::GalleryDetail);
+        assert_eq!(classify(r#"<form id="hathdl_form" action="https://e-hentai.org/archiver.php?gid=1&token=a&or=b" method="post"></form>"#), PageKind::ArchiveForm);
+        assert_eq!(classify(r#"<div class="ido"><div class="fp">...</div><table class="itg glte"></table></div>"#), PageKind::Favorites);
+        assert_eq!(classify(r#"<table class="itg gltc"></table>"#), PageKind::GalleryList);
+        assert_eq!(classify(r#"<div id="userlinks"></div>"#), PageKind::Forums);
+
+        assert_eq!(classify("<p>(And if you choose to ignore this warning, you lose all rights to complain about it in the future.)</p>"), PageKind::ContentWarning);
+        assert_eq!(classify("<p>This page requires you to log on.</p>"), PageKind::SignInRequired);
+        assert_eq!(classify("<div class=\"d\">\n<p>This gallery is pining for the fjords.</p></div>"), PageKind::GalleryRemoved);
+        assert_eq!(classify("Your IP address has been temporarily banned for excessive pageloads. The ban expires in 1 hour"), PageKind::IpBanned(Duration::from_secs(3600)));
+        assert_eq!(classify("<div class=\"d\">\n<p>Invalid page.</p></div>"), PageKind::ServerError(String::from("Invalid page.")));
+        assert_eq!(classify("<html></html>"), PageKind::Unknown);
+
+        assert!(matches!(classify("<p>This page requires you to log on.</p>").error_opt(), Some(ParseError::SignInRequired)));
+        assert!(PageKind::GalleryList.error_opt().is_none());
+    }
+}
@@ -0,0 +1,446 @@
+//! Describes the HTTP requests whose responses the parsers in [`crate::structures`] read.
+//! Nothing is sent from here, the caller hands a [`Request`] to the HTTP client of its choice.
+
+use serde_json::{json, Value};
+use crate::{eh_config, eh_url::{self, Site}, structures::{UConfigPage, UserTag}, url_encoding::encode_pairs};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    /// `application/x-www-form-urlencoded`
+    Form(Vec<(String, String)>),
+    /// `application/json`
+    Json(Value),
+}
+
+impl Body {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Body::Form(_) => "application/x-www-form-urlencoded",
+            Body::Json(_) => "application/json",
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        match self {
+            Body::Form(pairs) => encode_pairs(pairs),
+            Body::Json(value) => value.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: Method,
+    /// Without the query string, see [`Request::full_url`].
+    pub url: String,
+    pub query_vec: Vec<(String, String)>,
+    pub body_opt: Option<Body>,
+    pub header_vec: Vec<(String, String)>,
+    /// Cookies the page needs on top of the session cookies of the caller.
+    pub cookie_vec: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn get(url: &str) -> Self {
+        Request::new(Method::Get, url)
+    }
+
+    pub fn post(url: &str) -> Self {
+        Request::new(Method::Post, url)
+    }
+
+    fn new(method: Method, url: &str) -> Self {
+        Request {
+            method,
+            url: String::from(url),
+            query_vec: Vec::new(),
+            body_opt: None,
+            header_vec: Vec::new(),
+            cookie_vec: Vec::new(),
+        }
+    }
+
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        self.query_vec.push((String::from(key), String::from(value)));
+        self
+    }
+
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.header_vec.push((String::from(key), String::from(value)));
+        self
+    }
+
+    pub fn cookie(mut self, key: &str, value: &str) -> Self {
+        self.cookie_vec.push((String::from(key), String::from(value)));
+        self
+    }
+
+    pub fn form(mut self, pairs: Vec<(String, String)>) -> Self {
+        self.body_opt = Some(Body::Form(pairs));
+        self
+    }
+
+    pub fn json(mut self, value: Value) -> Self {
+        self.body_opt = Some(Body::Json(value));
+        self
+    }
+
+    /// `Referer` and `Origin` of the site, the api and the popups refuse posts without them.
+    fn site_headers(self, site: Site) -> Self {
+        self.header("Referer", site.referer())
+            .header("Origin", site.origin())
+    }
+
+    /// The url with the encoded query string appended.
+    pub fn full_url(&self) -> String {
+        if self.query_vec.is_empty() {
+            return self.url.clone();
+        }
+
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        format!("{}{}{}", self.url, separator, encode_pairs(&self.query_vec))
+    }
+
+    /// The value of the `Cookie` header for [`Request::cookie_vec`].
+    pub fn cookie_header(&self) -> String {
+        self.cookie_vec.iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+}
+
+/// [`crate::structures::Gallery`], the front page.
+pub fn gallery(site: Site) -> Request {
+    Request::get(site.host())
+}
+
+/// [`crate::structures::gallery_detail::GalleryDetail`].
+/// `preview_page` starts from 0, `all_comments` expands the comments below the threshold.
+pub fn gallery_detail(site: Site, gid: u64, token: &str, preview_page: u32, all_comments: bool) -> Request {
+    let mut request = Request::get(&format!("{}g/{}/{}/", site.host(), gid, token))
+        .cookie(eh_config::KEY_CONTENT_WARNING, eh_config::CONTENT_WARNING_NOT_SHOW);
+
+    if preview_page != 0 {
+        request = request.query("p", &preview_page.to_string());
+    }
+
+    if all_comments {
+        request = request.query("hc", "1");
+    }
+
+    request
+}
+
+/// [`crate::structures::GalleryPage`], `page` starts from 0.
+pub fn gallery_page(site: Site, gid: u64, p_token: &str, page: u32) -> Request {
+    Request::get(&format!("{}s/{}/{}-{}", site.host(), p_token, gid, page + 1))
+}
+
+/// [`crate::structures::GalleryMultiPageViewer`], for the users the site lets use it.
+pub fn gallery_multi_page_viewer(site: Site, gid: u64, token: &str) -> Request {
+    Request::get(&format!("{}mpv/{}/{}/", site.host(), gid, token))
+}
+
+/// [`crate::structures::Favorite`], `None` for all the slots.
+pub fn favorites(site: Site, slot_opt: Option<u32>) -> Request {
+    let request = Request::get(site.favorites());
+    match slot_opt {
+        Some(slot) => request.query("favcat", &slot.to_string()),
+        None => request,
+    }
+}
+
+/// Adds a gallery to the favorite `slot`, or moves it there.
+pub fn add_favorite(site: Site, gid: u64, token: &str, slot: u32, note: &str) -> Request {
+    favorite_popup(site, gid, token, slot.to_string(), note)
+}
+
+pub fn remove_favorite(site: Site, gid: u64, token: &str) -> Request {
+    favorite_popup(site, gid, token, String::from("favdel"), "")
+}
+
+fn favorite_popup(site: Site, gid: u64, token: &str, favcat: String, note: &str) -> Request {
+    Request::post(&format!("{}gallerypopups.php", site.host()))
+        .query("gid", &gid.to_string())
+        .query("t", token)
+        .query("act", "addfav")
+        .form(vec![
+            (String::from("favcat"), favcat),
+            (String::from("favnote"), String::from(note)),
+            (String::from("apply"), String::from("Apply Changes")),
+            (String::from("update"), String::from("1")),
+        ])
+        .site_headers(site)
+}
+
+/// [`crate::structures::Archive`], `archive_url` is the one found on the detail page.
+pub fn archive(archive_url: &str) -> Request {
+    Request::get(archive_url)
+}
+
+/// Asks the Hentai@Home client to download an archive, `res` is one of the [`crate::structures::Archive`] items.
+pub fn archive_download(archive_url: &str, res: &str) -> Request {
+    Request::post(archive_url)
+        .form(vec![(String::from("hathdl_xres"), String::from(res))])
+}
+
+/// [`crate::structures::Torrent`].
+pub fn torrent(site: Site, gid: u64, token: &str) -> Request {
+    Request::get(&format!("{}gallerytorrents.php", site.host()))
+        .query("gid", &gid.to_string())
+        .query("t", token)
+}
+
+/// [`crate::structures::SignIn`].
+pub fn sign_in(username: &str, password: &str) -> Request {
+    Request::post(eh_url::API_SIGN_IN)
+        .form(vec![
+            (String::from("referer"), String::from(eh_url::URL_SIGN_IN)),
+            (String::from("b"), String::new()),
+            (String::from("bt"), String::new()),
+            (String::from("UserName"), String::from(username)),
+            (String::from("PassWord"), String::from(password)),
+            (String::from("CookieDate"), String::from("1")),
+        ])
+        .header("Referer", eh_url::URL_SIGN_IN)
+}
+
+/// [`crate::structures::Forums`].
+pub fn forums() -> Request {
+    Request::get(eh_url::URL_FORUMS)
+}
+
+/// [`crate::structures::Profile`], `user_link` comes from [`crate::structures::Forums`].
+pub fn profile(user_link: &str) -> Request {
+    Request::get(user_link)
+}
+
+/// [`crate::structures::UConfigPage`].
+pub fn uconfig(site: Site) -> Request {
+    Request::get(site.uconfig())
+}
+
+/// Applies the settings of `page` to its selected profile, the response is the updated [`crate::structures::UConfigPage`].
+pub fn apply_uconfig(site: Site, page: &UConfigPage) -> Request {
+    Request::post(site.uconfig())
+        .form(page.form_vec())
+        .site_headers(site)
+}
+
+/// [`crate::structures::MyTags`], `None` for the set the site shows by default.
+pub fn my_tags(site: Site, tagset_opt: Option<u32>) -> Request {
+    let request = Request::get(site.my_tags());
+    match tagset_opt {
+        Some(tagset) => request.query("tagset", &tagset.to_string()),
+        None => request,
+    }
+}
+
+/// Adds `user_tag` to the set `tagset`, its id is ignored.
+pub fn add_user_tag(site: Site, tagset: u32, user_tag: &UserTag) -> Request {
+    let mut form = vec![
+        (String::from("usertag_action"), String::from("add")),
+        (String::from("tagname_new"), user_tag.tag.clone()),
+    ];
+    if user_tag.watched {
+        form.push((String::from("tagwatch_new"), String::from("on")));
+    }
+    if user_tag.hidden {
+        form.push((String::from("taghide_new"), String::from("on")));
+    }
+    form.extend([
+        (String::from("tagcolor_new"), user_tag.color_opt.clone().unwrap_or_default()),
+        (String::from("tagweight_new"), user_tag.weight.to_string()),
+        (String::from("usertag_target"), String::from("0")),
+    ]);
+
+    my_tags_form(site, tagset, form)
+}
+
+/// Deletes the tags `user_tag_id_vec` from the set `tagset`.
+pub fn delete_user_tags(site: Site, tagset: u32, user_tag_id_vec: &[u64]) -> Request {
+    let mut form = vec![
+        (String::from("usertag_action"), String::from("mass")),
+        (String::from("tagname_new"), String::new()),
+        (String::from("tagcolor_new"), String::new()),
+        (String::from("usertag_target"), String::from("0")),
+    ];
+    for id in user_tag_id_vec {
+        form.push((String::from("modify_usertags[]"), id.to_string()));
+    }
+
+    my_tags_form(site, tagset, form)
+}
+
+/// Shows or hides the tags of the set `tagset` on the site and sets their default color.
+pub fn update_tagset(site: Site, tagset: u32, enabled: bool, color_opt: Option<&str>) -> Request {
+    let mut form = vec![(String::from("tagset_action"), String::from("update"))];
+    if enabled {
+        form.push((String::from("tagset_enable"), String::from("on")));
+    }
+    form.push((String::from("tagset_color"), String::from(color_opt.unwrap_or_default())));
+
+    my_tags_form(site, tagset, form)
+}
+
+fn my_tags_form(site: Site, tagset: u32, form: Vec<(String, String)>) -> Request {
+    Request::post(site.my_tags())
+        .query("tagset", &tagset.to_string())
+        .form(form)
+        .site_headers(site)
+}
+
+/// Changes the watched, hidden, color and weight of an existing [`UserTag`], the page does it through the api.
+pub fn update_user_tag(site: Site, api_uid: u64, api_key: &str, user_tag: &UserTag) -> Request {
+    api(site, json!({
+        "method": "setusertag",
+        "apiuid": api_uid,
+        "apikey": api_key,
+        "tagid": user_tag.id,
+        "tagwatch": user_tag.watched as u32,
+        "taghide": user_tag.hidden as u32,
+        "tagcolor": user_tag.color_opt.clone().unwrap_or_default(),
+        "tagweight": user_tag.weight.to_string(),
+    }))
+}
+
+/// [`crate::structures::GalleryApi`], at most 25 galleries per request.
+pub fn gallery_api(site: Site, gallery_vec: &[(u64, &str)]) -> Request {
+    let gidlist = gallery_vec.iter()
+        .map(|(gid, token)| json!([gid, token]))
+        .collect::<Vec<Value>>();
+
+    api(site, json!({
+        "method": "gdata",
+        "gidlist": gidlist,
+        "namespace": 1,
+    }))
+}
+
+/// [`crate::structures::GalleryToken`], each page is `(gid, p_token, page)` and `page` starts from 0.
+pub fn gallery_token_api(site: Site, page_vec: &[(u64, &str, u32)]) -> Request {
+    let pagelist = page_vec.iter()
+        .map(|(gid, p_token, page)| json!([gid, p_token, page + 1]))
+        .collect::<Vec<Value>>();
+
+    api(site, json!({
+        "method": "gtoken",
+        "pagelist": pagelist,
+    }))
+}
+
+/// [`crate::structures::GalleryPageApi`], `show_key` comes from the first [`crate::structures::GalleryPage`].
+pub fn gallery_page_api(site: Site, gid: u64, p_token: &str, page: u32, show_key: &str) -> Request {
+    api(site, json!({
+        "method": "showpage",
+        "gid": gid,
+        "page": page + 1,
+        "imgkey": p_token,
+        "showkey": show_key,
+    }))
+}
+
+/// [`crate::structures::GalleryPageMpvApi`], `mpv_key` and `p_token` come from [`crate::structures::GalleryMultiPageViewer`].
+pub fn gallery_page_mpv_api(site: Site, gid: u64, p_token: &str, page: u32, mpv_key: &str) -> Request {
+    api(site, json!({
+        "method": "imagedispatch",
+        "gid": gid,
+        "page": page + 1,
+        "imgkey": p_token,
+        "mpvkey": mpv_key,
+    }))
+}
+
+/// [`crate::structures::RateGallery`], `rating` goes from 0.5 to 5 in steps of 0.5.
+pub fn rate_gallery(site: Site, api_uid: u64, api_key: &str, gid: u64, token: &str, rating: f32) -> Request {
+    api(site, json!({
+        "method": "rategallery",
+        "apiuid": api_uid,
+        "apikey": api_key,
+        "gid": gid,
+        "token": token,
+        "rating": (rating * 2.0).round() as u32,
+    }))
+}
+
+/// [`crate::structures::VoteComment`], `vote` is 1 or -1, voting twice the same way withdraws the vote.
+pub fn vote_comment(site: Site, api_uid: u64, api_key: &str, gid: u64, token: &str, comment_id: u64, vote: i32) -> Request {
+    api(site, json!({
+        "method": "votecomment",
+        "apiuid": api_uid,
+        "apikey": api_key,
+        "gid": gid,
+        "token": token,
+        "comment_id": comment_id,
+        "comment_vote": vote,
+    }))
+}
+
+/// [`crate::structures::VoteTag`], `tags` are `namespace:tag` separated by commas, `vote` is 1 or -1.
+pub fn vote_tag(site: Site, api_uid: u64, api_key: &str, gid: u64, token: &str, tags: &str, vote: i32) -> Request {
+    api(site, json!({
+        "method": "taggallery",
+        "apiuid": api_uid,
+        "apikey": api_key,
+        "gid": gid,
+        "token": token,
+        "tags": tags,
+        "vote": vote,
+    }))
+}
+
+fn api(site: Site, value: Value) -> Request {
+    Request::post(site.api())
+        .json(value)
+        .site_headers(site)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_url_test() {
+        let request = gallery_detail(Site::E, 2062874, "03037d8698", 2, true);
+        assert_eq!(request.full_url(), "https://e-hentai.org/g/2062874/03037d8698/?p=2&hc=1");
+        assert_eq!(request.cookie_header(), "nw=1");
+
+        let request = Request::get("https://e-hentai.org/").query("f_search", "female:\"big breasts$\"");
+        assert_eq!(request.full_url(), "https://e-hentai.org/?f_search=female%3A%22big+breasts%24%22");
+    }
+
+    #[test]
+    fn api_test() {
+        let request = gallery_api(Site::Ex, &[(618395, "0439fa3666")]);
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.url, "https://exhentai.org/api.php");
+        assert_eq!(request.body_opt, Some(Body::Json(json!({
+            "method": "gdata",
+            "gidlist": [[618395, "0439fa3666"]],
+            "namespace": 1,
+        }))));
+        assert!(request.header_vec.contains(&(String::from("Origin"), String::from("https://exhentai.org"))));
+    }
+
+    #[test]
+    fn form_test() {
+        let request = add_favorite(Site::E, 2062874, "03037d8698", 3, "to read");
+        let body = request.body_opt.unwrap();
+        assert_eq!(body.content_type(), "application/x-www-form-urlencoded");
+        assert_eq!(body.encode(), "favcat=3&favnote=to+read&apply=Apply+Changes&update=1");
+
+        let request = delete_user_tags(Site::Ex, 2, &[12345, 12346]);
+        assert_eq!(request.full_url(), "https://exhentai.org/mytags?tagset=2");
+        assert_eq!(
+            request.body_opt.unwrap().encode(),
+            "usertag_action=mass&tagname_new=&tagcolor_new=&usertag_target=0&modify_usertags%5B%5D=12345&modify_usertags%5B%5D=12346"
+        );
+    }
+}
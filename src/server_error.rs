@@ -0,0 +1,83 @@
+use std::time::Duration;
+use regex::Regex;
+use crate::ParseError;
+
+/// Recognizes the pages and the messages the site sends instead of an image page,
+/// `None` when `doc` is none of them.
+pub fn parse_server_error(doc: &str) -> Option<ParseError> {
+    if doc.contains(IP_BANNED_STRING) {
+        let regex = Regex::new(PATTERN_BAN_EXPIRES).unwrap();
+        let duration = regex.captures(doc)
+            .map(|captures| parse_duration(&captures[1]))
+            .unwrap_or_default();
+        return Some(ParseError::IpBanned(duration));
+    }
+
+    if IMAGE_LIMIT_STRING_LIST.iter().any(|string| doc.contains(string)) {
+        return Some(ParseError::ImageLimitExceeded);
+    }
+
+    if GALLERY_REMOVED_STRING_LIST.iter().any(|string| doc.contains(string)) {
+        return Some(ParseError::GalleryRemoved);
+    }
+
+    if KEY_EXPIRED_STRING_LIST.iter().any(|string| doc.contains(string)) {
+        return Some(ParseError::KeyExpired);
+    }
+
+    None
+}
+
+/// The 509 image takes the place of the image once the quota is used up.
+pub fn is_image_limit_url(image_url: &str) -> bool {
+    let regex = Regex::new(PATTERN_509_URL).unwrap();
+    regex.is_match(image_url)
+}
+
+/// `2 days, 23 hours and 59 minutes`.
+fn parse_duration(text: &str) -> Duration {
+    let regex = Regex::new(PATTERN_DURATION_PART).unwrap();
+    let seconds = regex.captures_iter(text)
+        .map(|captures| {
+            let number = captures[1].parse::<u64>().unwrap_or_default();
+            let unit = match &captures[2] {
+                "day" => 86400,
+                "hour" => 3600,
+                "minute" => 60,
+                _ => 1,
+            };
+            number * unit
+        })
+        .sum();
+
+    Duration::from_secs(seconds)
+}
+
+const IP_BANNED_STRING: &str = "Your IP address has been temporarily banned";
+const IMAGE_LIMIT_STRING_LIST: [&str; 2] = ["You have exceeded your image viewing limits", "You have reached the image limit"];
+const GALLERY_REMOVED_STRING_LIST: [&str; 2] = ["This gallery has been removed or is unavailable", "This gallery is currently unavailable"];
+const KEY_EXPIRED_STRING_LIST: [&str; 2] = ["Key missing, or incorrect key provided", "Key mismatch"];
+const PATTERN_BAN_EXPIRES: &str = r#"The ban expires in ([^.<]+)"#;
+const PATTERN_DURATION_PART: &str = r#"(\d+)\s*(day|hour|minute|second)s?"#;
+const PATTERN_509_URL: &str = r#"/509s?\.gif(?:$|\?)"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_server_error_test() {
+        let doc = "Your IP address has been temporarily banned for excessive pageloads which indicates that you are using automated mirroring/harvesting software. The ban expires in 2 days, 23 hours and 59 minutes";
+        assert!(matches!(parse_server_error(doc), Some(ParseError::IpBanned(duration)) if duration.as_secs() == 2 * 86400 + 23 * 3600 + 59 * 60));
+        let doc = "<p>You have exceeded your image viewing limits. Note that you can reset these limits by going <a href=\"https://e-hentai.org/home.php\">here</a>.</p>";
+        assert!(matches!(parse_server_error(doc), Some(ParseError::ImageLimitExceeded)));
+        let doc = "<div class=\"d\"><p>This gallery has been removed or is unavailable.</p></div>";
+        assert!(matches!(parse_server_error(doc), Some(ParseError::GalleryRemoved)));
+        assert!(matches!(parse_server_error("Key missing, or incorrect key provided."), Some(ParseError::KeyExpired)));
+        assert!(parse_server_error("<div id=\"i1\"></div>").is_none());
+
+        assert!(is_image_limit_url("https://ehgt.org/g/509.gif"));
+        assert!(is_image_limit_url("https://exhentai.org/img/509s.gif"));
+        assert!(!is_image_limit_url("https://abcdefg.hath.network/h/509.jpg"));
+    }
+}
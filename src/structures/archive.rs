@@ -1,13 +1,15 @@
 use regex::Regex;
-use crate::{EhResult, Parser, REGEX_MATCH_FAILED, unescape::unescape};
+use crate::{EhResult, Parser, REGEX_MATCH_FAILED, page_kind::pre_check, unescape::unescape};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Archive {
     pub or: String,
     pub items: Vec<Item>,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Item {
     pub res: String,
     pub name: String,
@@ -15,6 +17,8 @@ pub struct Item {
 
 impl Parser for Archive {
     fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
+
         let regex = Regex::new(PATTERN_FORM).unwrap();
         let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
         let or = String::from(&captures[1]);
@@ -49,4 +53,10 @@ mod tests {
         let doc = read_test_file("archive.html");
         assert_eq!(Archive::parse(&doc).is_ok(), true);
     }
+
+    #[test]
+    fn parse_pre_check_test() {
+        let doc = "Your IP address has been temporarily banned for excessive pageloads. The ban expires in 1 hour";
+        assert!(matches!(Archive::parse(doc), Err(crate::ParseError::IpBanned(_))));
+    }
 }
@@ -1,3 +1,4 @@
+use std::{fmt::{Display, Formatter}, ops::{BitAnd, BitOr, Not, Sub}};
 use crate::eh_config;
 
 trait IgnoreCase {
@@ -11,6 +12,7 @@ impl IgnoreCase for [&str] {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Category {
     pub color: u32,
     pub string: String,
@@ -60,6 +62,136 @@ impl From<u32> for Category {
     }
 }
 
+/// A set of the ten categories, as a mask of their values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CategorySet(u32);
+
+impl CategorySet {
+    pub const EMPTY: CategorySet = CategorySet(0);
+    pub const ALL: CategorySet = CategorySet(eh_config::ALL_CATEGORY);
+
+    /// The bits outside [`CategorySet::ALL`] are dropped.
+    pub fn new(value: u32) -> Self {
+        CategorySet(value & eh_config::ALL_CATEGORY)
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    /// `f_cats` of the search, the mask of the categories hidden.
+    pub fn from_f_cats(f_cats: u32) -> Self {
+        !CategorySet::new(f_cats)
+    }
+
+    pub fn to_f_cats(&self) -> u32 {
+        (!*self).0
+    }
+
+    /// The default categories of [`crate::structures::UConfig`], like `f_cats` the mask of the categories hidden.
+    pub fn from_default_categories(default_categories: u32) -> Self {
+        CategorySet::from_f_cats(default_categories)
+    }
+
+    pub fn to_default_categories(&self) -> u32 {
+        self.to_f_cats()
+    }
+
+    pub fn contains(&self, value: u32) -> bool {
+        value != 0 && self.0 & value == value
+    }
+
+    pub fn insert(&mut self, value: u32) {
+        *self = *self | CategorySet::new(value);
+    }
+
+    pub fn remove(&mut self, value: u32) {
+        *self = *self - CategorySet::new(value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The values of the categories in the set, from `MISC` to `WESTERN`.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        CATEGORY_VALUES[..10].iter().copied().filter(|value| self.contains(*value))
+    }
+
+    /// Like `Doujinshi` or `Artist CG`.
+    pub fn name_vec(&self) -> Vec<&'static str> {
+        self.iter().map(display_name).collect()
+    }
+}
+
+impl Default for CategorySet {
+    /// Every category, the search without filter.
+    fn default() -> Self {
+        CategorySet::ALL
+    }
+}
+
+impl From<u32> for CategorySet {
+    fn from(value: u32) -> Self {
+        CategorySet::new(value)
+    }
+}
+
+impl FromIterator<u32> for CategorySet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        CategorySet::new(iter.into_iter().fold(0, |mask, value| mask | value))
+    }
+}
+
+impl BitOr for CategorySet {
+    type Output = CategorySet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        CategorySet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for CategorySet {
+    type Output = CategorySet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        CategorySet(self.0 & rhs.0)
+    }
+}
+
+impl Sub for CategorySet {
+    type Output = CategorySet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        CategorySet(self.0 & !rhs.0)
+    }
+}
+
+impl Not for CategorySet {
+    type Output = CategorySet;
+
+    fn not(self) -> Self::Output {
+        CategorySet(eh_config::ALL_CATEGORY & !self.0)
+    }
+}
+
+impl Display for CategorySet {
+    /// The names separated by `, `.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name_vec().join(", "))
+    }
+}
+
+fn display_name(value: u32) -> &'static str {
+    let idx = CATEGORY_VALUES.iter().position(|v| *v == value).unwrap_or(10);
+    CATEGORY_DISPLAY_NAMES[idx]
+}
+
 // Use it for homepage
 // const NONE: i8 = -1;
 
@@ -104,6 +236,20 @@ const CATEGORY_VALUES: [u32; 11] = [
 
 const VALUE_UNKNOWN: u32 = 0x400;
 
+const CATEGORY_DISPLAY_NAMES: [&str; 11] = [
+    "Misc",
+    "Doujinshi",
+    "Manga",
+    "Artist CG",
+    "Game CG",
+    "Image Set",
+    "Cosplay",
+    "Asian Porn",
+    "Non-H",
+    "Western",
+    "Unknown",
+];
+
 const CATEGORY_STRINGS: [[&str; 3]; 11] = [
     ["misc", EMPTY_STRING, EMPTY_STRING],
     ["doujinshi", EMPTY_STRING, EMPTY_STRING],
@@ -131,3 +277,32 @@ const BG_COLOR_WESTERN: u32 = 0xff8bc34a;
 const BG_COLOR_UNKNOWN: u32 = 0x00000000;
 
 const EMPTY_STRING: &str = "";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_set_test() {
+        let mut set = CategorySet::new(eh_config::DOUJINSHI | eh_config::MANGA);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(eh_config::MANGA));
+        assert!(!set.contains(eh_config::WESTERN));
+        assert_eq!(set.to_string(), "Doujinshi, Manga");
+        assert_eq!(set.to_f_cats(), 1017);
+        assert_eq!(CategorySet::from_f_cats(1017), set);
+
+        set.insert(eh_config::ARTIST_CG);
+        set.remove(eh_config::DOUJINSHI);
+        assert_eq!(set.iter().collect::<Vec<u32>>(), vec![eh_config::MANGA, eh_config::ARTIST_CG]);
+        assert_eq!(set.name_vec(), vec!["Manga", "Artist CG"]);
+
+        let other = [eh_config::MANGA, eh_config::NON_H].into_iter().collect::<CategorySet>();
+        assert_eq!((set & other).value(), eh_config::MANGA);
+        assert_eq!((set | other).len(), 3);
+        assert_eq!((set - other).value(), eh_config::ARTIST_CG);
+        assert_eq!(!CategorySet::ALL, CategorySet::EMPTY);
+        assert_eq!(CategorySet::new(0xffff), CategorySet::ALL);
+        assert_eq!(CategorySet::from_default_categories(eh_config::MISC | eh_config::WESTERN).len(), 8);
+    }
+}
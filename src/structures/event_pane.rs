@@ -5,6 +5,7 @@ use crate::{EhResult, Parser};
 
 #[deprecated]
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventPane {
     pub value: String,
 }
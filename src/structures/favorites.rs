@@ -1,8 +1,11 @@
 use visdom::Vis;
 use crate::{
+    Context,
     EhResult,
+    ErrorContext,
+    ParseError,
     Parser,
-    SIGN_IN_REQUIRED,
+    page_kind::pre_check,
     structures::{
         SearchNav,
         gallery_list::GalleryList,
@@ -10,6 +13,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Favorite {
     pub search_nav: SearchNav,
     /// Size 10
@@ -21,9 +25,7 @@ pub struct Favorite {
 
 impl Parser for Favorite {
     fn parse(doc: &str) -> EhResult<Self> {
-        if doc.contains("This page requires you to log on.</p>") {
-            return Err(SIGN_IN_REQUIRED);
-        }
+        pre_check(doc)?;
 
         let mut cat_vec = Vec::new();
         let mut count_vec = Vec::new();
@@ -32,18 +34,23 @@ impl Parser for Favorite {
         // skip last one: <div class="fp fps"...
         let fps = root.find(".ido [class=fp]");
 
-        assert_eq!(fps.length(), 10);
+        if fps.length() != 10 {
+            return Err(ParseError::UnrecognizedLayout(format!("favorites, {} slots", fps.length())));
+        }
         for fp in fps {
             let children = fp.children();
             let count = children.eq(0);
             let cat = children.eq(2);
-            count_vec.push(count.text().parse::<u32>()?);
+            count_vec.push(count.text().parse::<u32>()
+                .with_context(|| ErrorContext::new(PARSER, "count_vec").locator(".ido [class=fp]").snippet(&fp.outer_html()))?);
             cat_vec.push(cat.text());
         }
 
         let nav = root.find(r#".searchnav"#).eq(0);
-        let search_nav = SearchNav::parse(&nav.outer_html())?;
-        let gallery_list = GalleryList::parse(doc)?;
+        let search_nav = SearchNav::parse(&nav.outer_html())
+            .context(ErrorContext::new(PARSER, "search_nav").locator(".searchnav"))?;
+        let gallery_list = GalleryList::parse(doc)
+            .context(ErrorContext::new(PARSER, "gallery_list"))?;
 
         Ok(Favorite {
             search_nav,
@@ -54,6 +61,8 @@ impl Parser for Favorite {
     }
 }
 
+const PARSER: &str = "Favorite";
+
 #[cfg(test)]
 mod tests {
     use crate::test_helper::read_test_file;
@@ -2,6 +2,7 @@ use visdom::Vis;
 use crate::{ATTRIBUTE_NOT_FOUND, EhResult, Parser};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Forums {
     /// Links to user profile page.
     pub user_link: String,
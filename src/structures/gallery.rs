@@ -1,7 +1,10 @@
 use visdom::Vis;
 use crate::{
+    Context,
     EhResult,
+    ErrorContext,
     Parser,
+    page_kind::pre_check,
     structures::{
         SearchNav,
         gallery_list::GalleryList,
@@ -9,6 +12,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gallery {
     pub search_nav: SearchNav,
     pub gallery_list: GalleryList,
@@ -16,11 +20,15 @@ pub struct Gallery {
 
 impl Parser for Gallery {
     fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
+
         let root = Vis::load(doc)?;
         let nav = root.find(r#".searchnav"#).eq(0);
-        let search_nav = SearchNav::parse(&nav.outer_html())?;
+        let search_nav = SearchNav::parse(&nav.outer_html())
+            .context(ErrorContext::new("Gallery", "search_nav").locator(".searchnav"))?;
 
-        let gallery_list = GalleryList::parse(doc)?;
+        let gallery_list = GalleryList::parse(doc)
+            .context(ErrorContext::new("Gallery", "gallery_list"))?;
 
         Ok(Gallery {
             search_nav,
@@ -31,7 +39,7 @@ impl Parser for Gallery {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_helper::read_test_file;
+    use crate::{ParseError, test_helper::read_test_file};
     use super::*;
 
     #[test]
@@ -51,4 +59,11 @@ mod tests {
         let doc = read_test_file("gallery_list_thumbnail.html");
         let result = Gallery::parse(&doc);
     }
+
+    #[test]
+    fn parse_pre_check_test() {
+        let doc = "Your IP address has been temporarily banned for excessive pageloads. The ban expires in 1 hour";
+        assert!(matches!(Gallery::parse(doc), Err(ParseError::IpBanned(duration)) if duration.as_secs() == 3600));
+        assert!(matches!(Gallery::parse(""), Err(ParseError::SignInRequired)));
+    }
 }
@@ -1 +1,297 @@
- 
+use serde::Deserialize;
+use crate::{EhResult, ParseError, Parser, structures::Category};
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryApi {
+    pub metadata_vec: Vec<GalleryMetadata>,
+    /// Entries the server refused, e.g. a wrong token.
+    pub error_vec: Vec<GalleryMetadataError>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryMetadata {
+    pub gid: u64,
+    pub token: String,
+    pub archiver_key: String,
+    pub title: String,
+    pub title_jpn: String,
+    pub category: u32,
+    pub thumb: String,
+    pub uploader: String,
+    pub posted_timestamp: i64,
+    pub file_count: u32,
+    pub file_size: u64,
+    pub expunged: bool,
+    pub rating: f32,
+    pub torrent_count: u32,
+    pub torrent_vec: Vec<GalleryMetadataTorrent>,
+    /// `namespace:tag`, tags without namespace are kept as is.
+    pub tag_vec: Vec<String>,
+    pub parent_gid_opt: Option<u64>,
+    pub parent_key_opt: Option<String>,
+    pub first_gid_opt: Option<u64>,
+    pub first_key_opt: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryMetadataTorrent {
+    pub hash: String,
+    pub added_timestamp: i64,
+    pub name: String,
+    pub torrent_size: u64,
+    pub file_size: u64,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryMetadataError {
+    pub gid: u64,
+    pub error: String,
+}
+
+impl Parser for GalleryApi {
+    /// ```json
+    /// {
+    ///     "gmetadata": [
+    ///         {
+    ///             "gid": 618395,
+    ///             "token": "0439fa3666",
+    ///             "archiver_key": "403565--d887c6dfe8aae79ed0071551aa1bafeb4a5ee361",
+    ///             "title": "(Kouroukan 8) [Handful☆Happiness! (Fuyuki Nanahara)] TOUHOU GUNMANIA A2 (Touhou Project)",
+    ///             "title_jpn": "(紅楼夢8) [Handful☆Happiness! (七原冬雪)] TOUHOU GUNMANIA A2 (東方Project)",
+    ///             "category": "Non-H",
+    ///             "thumb": "https://ehgt.org/14/63/1463dfbc16847c9ebef92c46a90e21ca881b2a12-1729712-4271-6032-jpg_l.jpg",
+    ///             "uploader": "avexotsukaai",
+    ///             "posted": "1376143500",
+    ///             "filecount": "20",
+    ///             "filesize": 51210504,
+    ///             "expunged": false,
+    ///             "rating": "4.43",
+    ///             "torrentcount": "0",
+    ///             "torrents": [],
+    ///             "tags": [
+    ///                 "parody:touhou project",
+    ///                 "artbook"
+    ///             ]
+    ///         },
+    ///         {
+    ///             "gid": 1,
+    ///             "error": "Key missing, or incorrect key provided."
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    /// Or
+    /// ```json
+    /// {
+    ///     "error": "Invalid request."
+    /// }
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let internal = serde_json::from_str::<GalleryApiInternal>(doc)?;
+        if let Some(error) = internal.error {
+            return Err(ParseError::FromServer(error));
+        }
+
+        let mut metadata_vec = Vec::new();
+        let mut error_vec = Vec::new();
+        for entry in internal.gmetadata.unwrap_or_default() {
+            match entry {
+                MetadataEntryInternal::Error { gid, error } => {
+                    error_vec.push(GalleryMetadataError { gid, error });
+                }
+                MetadataEntryInternal::Metadata(metadata) => {
+                    metadata_vec.push(metadata.try_into()?);
+                }
+            }
+        }
+
+        Ok(GalleryApi {
+            metadata_vec,
+            error_vec,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GalleryApiInternal {
+    gmetadata: Option<Vec<MetadataEntryInternal>>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum MetadataEntryInternal {
+    Error {
+        gid: u64,
+        error: String,
+    },
+    Metadata(Box<MetadataInternal>),
+}
+
+/// Most of the numbers are sent as strings.
+#[derive(Debug, Deserialize)]
+struct MetadataInternal {
+    gid: u64,
+    token: String,
+    archiver_key: String,
+    title: String,
+    title_jpn: String,
+    category: String,
+    thumb: String,
+    uploader: String,
+    posted: String,
+    filecount: String,
+    filesize: u64,
+    expunged: bool,
+    rating: String,
+    torrentcount: String,
+    #[serde(default)]
+    torrents: Vec<TorrentInternal>,
+    #[serde(default)]
+    tags: Vec<String>,
+    parent_gid: Option<String>,
+    parent_key: Option<String>,
+    first_gid: Option<String>,
+    first_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TorrentInternal {
+    hash: String,
+    added: String,
+    name: String,
+    tsize: String,
+    fsize: String,
+}
+
+impl TryFrom<Box<MetadataInternal>> for GalleryMetadata {
+    type Error = ParseError;
+
+    fn try_from(value: Box<MetadataInternal>) -> Result<Self, Self::Error> {
+        let mut torrent_vec = Vec::new();
+        for torrent in value.torrents {
+            torrent_vec.push(GalleryMetadataTorrent {
+                hash: torrent.hash,
+                added_timestamp: torrent.added.parse()?,
+                name: torrent.name,
+                torrent_size: torrent.tsize.parse()?,
+                file_size: torrent.fsize.parse()?,
+            });
+        }
+
+        let parent_gid_opt = match value.parent_gid {
+            Some(parent_gid) => Some(parent_gid.parse()?),
+            None => None,
+        };
+
+        let first_gid_opt = match value.first_gid {
+            Some(first_gid) => Some(first_gid.parse()?),
+            None => None,
+        };
+
+        Ok(GalleryMetadata {
+            gid: value.gid,
+            token: value.token,
+            archiver_key: value.archiver_key,
+            title: value.title,
+            title_jpn: value.title_jpn,
+            category: Category::from(&value.category).value,
+            thumb: value.thumb,
+            uploader: value.uploader,
+            posted_timestamp: value.posted.parse()?,
+            file_count: value.filecount.parse()?,
+            file_size: value.filesize,
+            expunged: value.expunged,
+            rating: value.rating.parse()?,
+            torrent_count: value.torrentcount.parse()?,
+            torrent_vec,
+            tag_vec: value.tags,
+            parent_gid_opt,
+            parent_key_opt: value.parent_key,
+            first_gid_opt,
+            first_key_opt: value.first_key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eh_config;
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let json = r#"
+            {
+                "gmetadata": [
+                    {
+                        "gid": 618395,
+                        "token": "0439fa3666",
+                        "archiver_key": "403565--d887c6dfe8aae79ed0071551aa1bafeb4a5ee361",
+                        "title": "(Kouroukan 8) [Handful☆Happiness! (Fuyuki Nanahara)] TOUHOU GUNMANIA A2 (Touhou Project)",
+                        "title_jpn": "(紅楼夢8) [Handful☆Happiness! (七原冬雪)] TOUHOU GUNMANIA A2 (東方Project)",
+                        "category": "Non-H",
+                        "thumb": "https://ehgt.org/14/63/1463dfbc16847c9ebef92c46a90e21ca881b2a12-1729712-4271-6032-jpg_l.jpg",
+                        "uploader": "avexotsukaai",
+                        "posted": "1376143500",
+                        "filecount": "20",
+                        "filesize": 51210504,
+                        "expunged": false,
+                        "rating": "4.43",
+                        "torrentcount": "1",
+                        "torrents": [
+                            {
+                                "hash": "0b4a0e8c4e9e4f4b0b4a0e8c4e9e4f4b0b4a0e8c",
+                                "added": "1376143600",
+                                "name": "TOUHOU GUNMANIA A2.zip",
+                                "tsize": "1024",
+                                "fsize": "51210504"
+                            }
+                        ],
+                        "tags": [
+                            "parody:touhou project",
+                            "artbook"
+                        ],
+                        "parent_gid": "618000",
+                        "parent_key": "a1b2c3d4e5",
+                        "first_gid": "617000",
+                        "first_key": "f6e5d4c3b2"
+                    },
+                    {
+                        "gid": 1,
+                        "error": "Key missing, or incorrect key provided."
+                    }
+                ]
+            }
+        "#;
+
+        let result = GalleryApi::parse(json).unwrap();
+        assert_eq!(result.error_vec, vec![GalleryMetadataError {
+            gid: 1,
+            error: String::from("Key missing, or incorrect key provided."),
+        }]);
+
+        let metadata = &result.metadata_vec[0];
+        assert_eq!(metadata.gid, 618395);
+        assert_eq!(metadata.category, eh_config::NON_H);
+        assert_eq!(metadata.posted_timestamp, 1376143500);
+        assert_eq!(metadata.file_count, 20);
+        assert_eq!(metadata.rating, 4.43);
+        assert_eq!(metadata.torrent_vec[0].file_size, 51210504);
+        assert_eq!(metadata.tag_vec, vec!["parody:touhou project", "artbook"]);
+        assert_eq!(metadata.parent_gid_opt, Some(618000));
+        assert_eq!(metadata.first_key_opt.as_deref(), Some("f6e5d4c3b2"));
+
+        let json = r#"
+            {
+                "error": "Invalid request."
+            }
+        "#;
+
+        assert!(matches!(GalleryApi::parse(json), Err(ParseError::FromServer(_))));
+    }
+}
@@ -0,0 +1,246 @@
+use visdom::types::{BoxDynElement, IEnumTyped};
+use crate::{Parser, structures::{GalleryDetailUrl, GalleryPageUrl}};
+
+/// A piece of the body of a [`crate::structures::gallery_detail::GalleryComment`],
+/// the tags it does not tell apart are replaced by their content.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommentNode {
+    Text(String),
+    LineBreak,
+    Bold(Vec<CommentNode>),
+    Italic(Vec<CommentNode>),
+    Link {
+        url: String,
+        target: CommentLinkTarget,
+        node_vec: Vec<CommentNode>,
+    },
+    Image(String),
+    /// A span hiding its text until hovered, painted in the color of its background.
+    Spoiler(Vec<CommentNode>),
+}
+
+/// Where a [`CommentNode::Link`] leads.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommentLinkTarget {
+    Gallery(GalleryDetailUrl),
+    Page(GalleryPageUrl),
+    Other,
+}
+
+impl CommentNode {
+    /// Reads the children of the `.c6` of a comment, the only walk of the body:
+    /// [`html`], [`text`] and [`link_vec`] render this tree.
+    ///
+    /// ```html
+    /// <div class="c6" id="comment_3922747"><b>RAW</b>: <a href="https://e-hentai.org/g/1378957/7f626bf1d2/">here</a><br>
+    ///     <span style="color:transparent; background-color:black">spoiler</span></div>
+    /// ```
+    pub(crate) fn parse_vec(ele: &BoxDynElement) -> Vec<CommentNode> {
+        let mut node_vec = Vec::new();
+        for node in ele.child_nodes() {
+            let child = match node.typed() {
+                IEnumTyped::Element(child) => child,
+                IEnumTyped::Text(text) => {
+                    push_text(&mut node_vec, &text.text_content());
+                    continue;
+                }
+                IEnumTyped::UncareNode(_) => continue,
+            };
+
+            let url_opt = |name: &str| child.get_attribute(name)
+                .map(|value| value.to_string())
+                .filter(|url| url.starts_with("https://") || url.starts_with("http://"));
+            match child.tag_name().to_lowercase().as_str() {
+                "br" => node_vec.push(CommentNode::LineBreak),
+                "script" | "style" => {}
+                "b" | "strong" => node_vec.push(CommentNode::Bold(CommentNode::parse_vec(&child))),
+                "i" | "em" => node_vec.push(CommentNode::Italic(CommentNode::parse_vec(&child))),
+                "img" => {
+                    if let Some(src) = url_opt("src") {
+                        node_vec.push(CommentNode::Image(src));
+                    }
+                }
+                "a" => match url_opt("href") {
+                    Some(url) => {
+                        let target = CommentLinkTarget::parse(&url);
+                        node_vec.push(CommentNode::Link { url, target, node_vec: CommentNode::parse_vec(&child) });
+                    }
+                    None => append(&mut node_vec, CommentNode::parse_vec(&child)),
+                },
+                "span" if is_spoiler(&child) => node_vec.push(CommentNode::Spoiler(CommentNode::parse_vec(&child))),
+                _ => append(&mut node_vec, CommentNode::parse_vec(&child)),
+            }
+        }
+
+        node_vec
+    }
+}
+
+impl CommentLinkTarget {
+    pub fn parse(url: &str) -> Self {
+        if let Ok(gallery_detail_url) = GalleryDetailUrl::parse(url) {
+            CommentLinkTarget::Gallery(gallery_detail_url)
+        } else if let Ok(gallery_page_url) = GalleryPageUrl::parse(url) {
+            CommentLinkTarget::Page(gallery_page_url)
+        } else {
+            CommentLinkTarget::Other
+        }
+    }
+}
+
+/// The site hides the spoilers by painting the text in the color of its background.
+fn is_spoiler(ele: &BoxDynElement) -> bool {
+    let class = ele.get_attribute("class").map(|class| class.to_string()).unwrap_or_default();
+    let style = ele.get_attribute("style").map(|style| style.to_string()).unwrap_or_default().replace(' ', "");
+    class.split_whitespace().any(|class| class == "spoiler") || style.contains("color:transparent")
+}
+
+/// Merges the text with the text before it, the content of a dropped tag would split it otherwise.
+fn push_text(node_vec: &mut Vec<CommentNode>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match node_vec.last_mut() {
+        Some(CommentNode::Text(last)) => last.push_str(text),
+        _ => node_vec.push(CommentNode::Text(String::from(text))),
+    }
+}
+
+/// Drops the indent around the body, like [`str::trim`].
+pub(crate) fn trim(mut node_vec: Vec<CommentNode>) -> Vec<CommentNode> {
+    if let Some(CommentNode::Text(text)) = node_vec.first_mut() {
+        *text = String::from(text.trim_start());
+    }
+    if let Some(CommentNode::Text(text)) = node_vec.last_mut() {
+        *text = String::from(text.trim_end());
+    }
+    node_vec.retain(|node| !matches!(node, CommentNode::Text(text) if text.is_empty()));
+    node_vec
+}
+
+/// The body as html, the spoilers as `<span class="spoiler">`, every text escaped.
+pub(crate) fn html(node_vec: &[CommentNode]) -> String {
+    let mut html = String::new();
+    for node in node_vec {
+        match node {
+            CommentNode::Text(text) => html.push_str(&escape(text)),
+            CommentNode::LineBreak => html.push_str("<br>"),
+            CommentNode::Bold(node_vec) => html.push_str(&format!("<b>{}</b>", self::html(node_vec))),
+            CommentNode::Italic(node_vec) => html.push_str(&format!("<i>{}</i>", self::html(node_vec))),
+            CommentNode::Link { url, node_vec, .. } => {
+                html.push_str(&format!(r#"<a href="{}">{}</a>"#, escape(url), self::html(node_vec)));
+            }
+            CommentNode::Image(src) => html.push_str(&format!(r#"<img src="{}">"#, escape(src))),
+            CommentNode::Spoiler(node_vec) => {
+                html.push_str(&format!(r#"<span class="spoiler">{}</span>"#, self::html(node_vec)));
+            }
+        }
+    }
+    html
+}
+
+/// The body as text, a line break for every `<br>`, the images left out.
+pub(crate) fn text(node_vec: &[CommentNode]) -> String {
+    let mut text = String::new();
+    for node in node_vec {
+        match node {
+            CommentNode::Text(content) => text.push_str(content),
+            CommentNode::LineBreak => text.push('\n'),
+            CommentNode::Bold(node_vec)
+            | CommentNode::Italic(node_vec)
+            | CommentNode::Link { node_vec, .. }
+            | CommentNode::Spoiler(node_vec) => text.push_str(&self::text(node_vec)),
+            CommentNode::Image(_) => {}
+        }
+    }
+    text
+}
+
+/// The url of every link, in order.
+pub(crate) fn link_vec(node_vec: &[CommentNode]) -> Vec<String> {
+    let mut link_vec = Vec::new();
+    for node in node_vec {
+        match node {
+            CommentNode::Link { url, node_vec, .. } => {
+                link_vec.push(url.clone());
+                link_vec.extend(self::link_vec(node_vec));
+            }
+            CommentNode::Bold(node_vec) | CommentNode::Italic(node_vec) | CommentNode::Spoiler(node_vec) => {
+                link_vec.extend(self::link_vec(node_vec));
+            }
+            CommentNode::Text(_) | CommentNode::LineBreak | CommentNode::Image(_) => {}
+        }
+    }
+    link_vec
+}
+
+fn escape(str: &str) -> String {
+    str.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn append(node_vec: &mut Vec<CommentNode>, other_vec: Vec<CommentNode>) {
+    for node in other_vec {
+        match node {
+            CommentNode::Text(text) => push_text(node_vec, &text),
+            node => node_vec.push(node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use visdom::Vis;
+    use super::*;
+
+    #[test]
+    fn parse_vec_test() {
+        let ele = r#"
+            <div class="c6" id="comment_3922747"><b>RAW</b>: <a href="https://e-hentai.org/g/1378957/7f626bf1d2/">here</a><br><u>page</u> <a
+                href="https://e-hentai.org/s/35142216f7/2062874-16"><i>16</i></a><br><span style="color: transparent; background-color:black">spoiler</span><a
+                href="https://forums.e-hentai.org/">forums</a><img src="https://ehgt.org/g/ygm.png"><script>alert(1)</script></div>
+        "#;
+        let root = Vis::load(ele).unwrap();
+        let node_vec = CommentNode::parse_vec(root.find(".c6").get(0).unwrap());
+
+        assert_eq!(trim(node_vec), vec![
+            CommentNode::Bold(vec![CommentNode::Text(String::from("RAW"))]),
+            CommentNode::Text(String::from(": ")),
+            CommentNode::Link {
+                url: String::from("https://e-hentai.org/g/1378957/7f626bf1d2/"),
+                target: CommentLinkTarget::Gallery(GalleryDetailUrl { gid: 1378957, token: String::from("7f626bf1d2") }),
+                node_vec: vec![CommentNode::Text(String::from("here"))],
+            },
+            CommentNode::LineBreak,
+            CommentNode::Text(String::from("page ")),
+            CommentNode::Link {
+                url: String::from("https://e-hentai.org/s/35142216f7/2062874-16"),
+                target: CommentLinkTarget::Page(GalleryPageUrl { gid: 2062874, p_token: String::from("35142216f7"), page: 15 }),
+                node_vec: vec![CommentNode::Italic(vec![CommentNode::Text(String::from("16"))])],
+            },
+            CommentNode::LineBreak,
+            CommentNode::Spoiler(vec![CommentNode::Text(String::from("spoiler"))]),
+            CommentNode::Link {
+                url: String::from("https://forums.e-hentai.org/"),
+                target: CommentLinkTarget::Other,
+                node_vec: vec![CommentNode::Text(String::from("forums"))],
+            },
+            CommentNode::Image(String::from("https://ehgt.org/g/ygm.png")),
+        ]);
+    }
+
+    #[test]
+    fn render_test() {
+        let ele = r#"<div class="c6"><b>a &amp; b</b><br><span class="spoiler"><i>c</i> <a href="https://e-hentai.org/">"d"</a></span><img src="https://ehgt.org/g/ygm.png"></div>"#;
+        let root = Vis::load(ele).unwrap();
+        let node_vec = CommentNode::parse_vec(root.find(".c6").get(0).unwrap());
+
+        assert_eq!(
+            html(&node_vec),
+            r#"<b>a &amp; b</b><br><span class="spoiler"><i>c</i> <a href="https://e-hentai.org/">&quot;d&quot;</a></span><img src="https://ehgt.org/g/ygm.png">"#,
+        );
+        assert_eq!(text(&node_vec), "a & b\nc \"d\"");
+        assert_eq!(link_vec(&node_vec), vec![String::from("https://e-hentai.org/")]);
+    }
+}
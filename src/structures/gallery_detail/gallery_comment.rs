@@ -1,9 +1,10 @@
-use chrono::DateTime;
 use regex::Regex;
 use visdom::Vis;
-use crate::{EhResult, Parser, REGEX_MATCH_FAILED};
+use crate::{Context, EhResult, ErrorContext, Parser, REGEX_MATCH_FAILED, datetime::parse_datetime};
+use super::{CommentNode, comment_node};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryComment {
     /// 0 for uploader comment. cannot vote.
     pub id: u64,
@@ -16,13 +17,37 @@ pub struct GalleryComment {
     pub vote_down_ed: bool,
     pub is_uploader: bool,
     /// uploader comment is `None`.
-    pub vote_state_opt: Option<String>,
+    pub vote_state_opt: Option<GalleryCommentVoteState>,
     pub posted_timestamp: i64,
     pub user: String,
+    /// The html of [`GalleryComment::comment_node_vec`], the formatting, the links and the images kept.
     pub comment: String,
+    /// The text of [`GalleryComment::comment_node_vec`], a line break for every `<br>`.
+    pub comment_text: String,
+    /// The url of every link of [`GalleryComment::comment_node_vec`], in order.
+    pub link_vec: Vec<String>,
+    /// The body as a tree, for rendering it without html.
+    pub comment_node_vec: Vec<CommentNode>,
     pub last_edited_timestamp_opt: Option<i64>,
 }
 
+/// The breakdown of the score, `Base +3, <span>q171718988 +3</span>, ..., and 38 more...`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryCommentVoteState {
+    pub base: i32,
+    pub vote_vec: Vec<GalleryCommentVote>,
+    /// The voters the site leaves out of the list.
+    pub more_count: u32,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryCommentVote {
+    pub user: String,
+    pub delta: i32,
+}
+
 impl ToString for GalleryComment {
     fn to_string(&self) -> String {
         if let Some(score) = self.score_opt {
@@ -83,39 +108,64 @@ impl Parser for GalleryComment {
     fn parse(doc: &str) -> EhResult<Self> {
         const PATTERN_COMMENT_ID: &str = r#"<a name="c(\d+)"></a>"#;
         const PATTERN_COMMENT_DATETIME: &str = r#"Posted\s*on\s*(.+?)\s*by"#;
+        const PATTERN_COMMENT_LAST_EDITED: &str = r#"Last\s*edited\s*on\s*(.+?)\.?\s*$"#;
 
         let root = Vis::load(doc)?;
 
+        let context = |field: &str, locator: &str, doc: &str| {
+            ErrorContext::new(PARSER, field).locator(locator).snippet(doc)
+        };
+
         let regex = Regex::new(PATTERN_COMMENT_ID).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("id", PATTERN_COMMENT_ID, doc))?;
 
         // c0 is uploader comment. cannot vote.
         // id.
-        let id = captures[1].parse()?;
+        let id = captures[1].parse::<u64>()
+            .with_context(|| context("id", PATTERN_COMMENT_ID, doc))?;
 
         let c3 = root.find(".c3");
         let posted = c3.text();
 
         // posted_timestamp.
         let regex = Regex::new(PATTERN_COMMENT_DATETIME).unwrap();
-        let captures = regex.captures(&posted).ok_or(REGEX_MATCH_FAILED)?;
+        let captures = regex.captures(&posted)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("posted_timestamp", PATTERN_COMMENT_DATETIME, &posted))?;
 
-        let fmt = "%d %B %Y, %H:%M:%S%.3f %z";
-        let date_str = format!("{}:00.000 +0000", &captures[1]);
-        let datetime = DateTime::parse_from_str(&date_str, fmt)?;
-        let posted_timestamp = datetime.timestamp();
+        let posted_timestamp = parse_datetime(&captures[1])
+            .with_context(|| context("posted_timestamp", PATTERN_COMMENT_DATETIME, &captures[1]))?
+            .timestamp();
 
         // user.
         let a = c3.children("a");
         let user = a.text();
 
-        // comment.
+        // comment, comment_text, link_vec, comment_node_vec.
         let c6 = root.find(".c6");
-        let comment = c6.html();
+        let comment_node_vec = c6.get(0)
+            .map(|ele| comment_node::trim(CommentNode::parse_vec(ele)))
+            .unwrap_or_default();
+        let comment = comment_node::html(&comment_node_vec);
+        let comment_text = comment_node::text(&comment_node_vec);
+        let link_vec = comment_node::link_vec(&comment_node_vec);
 
         // last_edited_timestamp_opt.
         let c8 = root.find(".c8");
-        let last_edited_timestamp_opt = if !c8.is_empty() { Some(posted_timestamp) } else { None };
+        let last_edited_timestamp_opt = if !c8.is_empty() {
+            let last_edited = c8.text();
+            let regex = Regex::new(PATTERN_COMMENT_LAST_EDITED).unwrap();
+            let captures = regex.captures(&last_edited)
+                .ok_or(REGEX_MATCH_FAILED)
+                .with_context(|| context("last_edited_timestamp_opt", PATTERN_COMMENT_LAST_EDITED, &last_edited))?;
+            let last_edited_datetime = parse_datetime(&captures[1])
+                .with_context(|| context("last_edited_timestamp_opt", PATTERN_COMMENT_LAST_EDITED, &captures[1]))?;
+            Some(last_edited_datetime.timestamp())
+        } else {
+            None
+        };
 
         // is_uploader.
         let c4 = root.find(".c4");
@@ -156,11 +206,15 @@ impl Parser for GalleryComment {
 
             // vote_state_opt.
             let c7 = root.find(".c7");
-            vote_state_opt = Some(c7.text());
+            vote_state_opt = Some(parse_vote_state(&c7.html())
+                .with_context(|| context("vote_state_opt", ".c7", &c7.text()))?);
 
             // score_opt.
-            let span = root.find(&format!(r#".c5 #comment_score_{}"#, id));
-            score_opt = Some(span.text()[1..].parse()?);
+            let selector = format!(r#".c5 #comment_score_{}"#, id);
+            let span = root.find(&selector);
+            let score = span.text();
+            score_opt = Some(score.get(1..).unwrap_or_default().parse()
+                .with_context(|| context("score_opt", &selector, &score))?);
         }
 
 
@@ -177,11 +231,63 @@ impl Parser for GalleryComment {
             posted_timestamp,
             user,
             comment,
+            comment_text,
+            link_vec,
+            comment_node_vec,
             last_edited_timestamp_opt,
         })
     }
 }
 
+/// ```html
+/// Base +3, <span>q171718988 +3</span>, <span>Igarashi
+///         Shioya +6</span>, <span>DaweiX +4</span>, and 38 more...
+/// ```
+/// The breakdown is only shown, the spans it cannot read are skipped.
+fn parse_vote_state(doc: &str) -> EhResult<GalleryCommentVoteState> {
+    let root = Vis::load(doc)?;
+    let text = root.text();
+
+    let regex = Regex::new(PATTERN_VOTE_BASE).unwrap();
+    let base = match regex.captures(&text) {
+        Some(captures) => captures[1].parse()?,
+        None => 0,
+    };
+
+    let regex = Regex::new(PATTERN_VOTE).unwrap();
+    let mut vote_vec = Vec::new();
+    for span in root.find("span") {
+        let text = span.text();
+        let Some(captures) = regex.captures(&text) else {
+            continue;
+        };
+        let Ok(delta) = captures[2].parse() else {
+            continue;
+        };
+        vote_vec.push(GalleryCommentVote {
+            user: captures[1].split_whitespace().collect::<Vec<&str>>().join(" "),
+            delta,
+        });
+    }
+
+    let regex = Regex::new(PATTERN_VOTE_MORE).unwrap();
+    let more_count = match regex.captures(&text) {
+        Some(captures) => captures[1].parse()?,
+        None => 0,
+    };
+
+    Ok(GalleryCommentVoteState {
+        base,
+        vote_vec,
+        more_count,
+    })
+}
+
+const PARSER: &str = "GalleryComment";
+const PATTERN_VOTE_BASE: &str = r#"Base\s*([+-]\d+)"#;
+const PATTERN_VOTE: &str = r#"(?s)^\s*(.+?)\s*([+-]\d+)\s*$"#;
+const PATTERN_VOTE_MORE: &str = r#"and\s*(\d+)\s*more"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,5 +341,65 @@ mod tests {
             </div>
         "##;
         assert_eq!(GalleryComment::parse(ele).is_ok(), true);
+
+        let comment = GalleryComment::parse(ele).unwrap();
+        assert_eq!(comment.posted_timestamp, 1600941300);
+        assert_eq!(comment.last_edited_timestamp_opt, None);
+        assert_eq!(comment.comment, "猎 妈 人");
+
+        let vote_state = comment.vote_state_opt.unwrap();
+        assert_eq!(vote_state.base, 3);
+        assert_eq!(vote_state.vote_vec.len(), 12);
+        assert_eq!(vote_state.vote_vec[1], GalleryCommentVote { user: String::from("Igarashi Shioya"), delta: 6 });
+        assert_eq!(vote_state.more_count, 38);
+
+        // edited.
+        let ele = r##"
+            <a name="c3922746"></a>
+            <div class="c1">
+                <div class="c2">
+                    <div class="c3">Posted on 24 September 2020, 09:55 by: &nbsp; <a
+                            href="https://e-hentai.org/uploader/xxxx">xxxx</a></div>
+                    <div class="c4 nosel">[<a href="#" onclick="edit_comment(3922746); return false">Edit</a>]</div>
+                    <div class="c5 nosel">Score <span id="comment_score_3922746" style="opacity:1.0">+3</span></div>
+                    <div class="c"></div>
+                </div>
+                <div class="c6" id="comment_3922746">edited</div>
+                <div class="c8">Last edited on <strong>25 September 2020, 10:12</strong>.</div>
+                <div class="c7" id="cvotes_3922746" style="display:none">Base +3</div>
+            </div>
+        "##;
+        let comment = GalleryComment::parse(ele).unwrap();
+        assert!(comment.editable);
+        assert_eq!(comment.posted_timestamp, 1600941300);
+        assert_eq!(comment.last_edited_timestamp_opt, Some(1601028720));
+        assert_eq!(comment.vote_state_opt, Some(GalleryCommentVoteState { base: 3, vote_vec: vec![], more_count: 0 }));
+    }
+
+    #[test]
+    fn parse_comment_test() {
+        let ele = r##"
+            <a name="c3922747"></a>
+            <div class="c1">
+                <div class="c2">
+                    <div class="c3">Posted on 24 September 2020, 09:55 by: &nbsp; <a
+                            href="https://e-hentai.org/uploader/xxxx">xxxx</a></div>
+                    <div class="c4 nosel"></div>
+                    <div class="c5 nosel">Score <span id="comment_score_3922747" style="opacity:1.0">-2</span></div>
+                    <div class="c"></div>
+                </div>
+                <div class="c6" id="comment_3922747"><b>RAW</b>: <a href="https://e-hentai.org/g/1378957/7f626bf1d2/" onclick="alert(1)">here</a><br><span style="color:red">1 &lt; 2</span><script>alert(1)</script><a href="javascript:alert(1)">bad</a><img src="https://ehgt.org/g/ygm.png" onerror="alert(1)"></div>
+                <div class="c7" id="cvotes_3922747" style="display:none">Base +3, <span>a b -5</span>, <span>???</span></div>
+            </div>
+        "##;
+        let comment = GalleryComment::parse(ele).unwrap();
+        assert_eq!(
+            comment.comment,
+            r#"<b>RAW</b>: <a href="https://e-hentai.org/g/1378957/7f626bf1d2/">here</a><br>1 &lt; 2bad<img src="https://ehgt.org/g/ygm.png">"#,
+        );
+        assert_eq!(comment.comment_text, "RAW: here\n1 < 2bad");
+        assert_eq!(comment.link_vec, vec![String::from("https://e-hentai.org/g/1378957/7f626bf1d2/")]);
+        assert_eq!(comment.comment_node_vec.len(), 6);
+        assert_eq!(comment.vote_state_opt.unwrap().vote_vec, vec![GalleryCommentVote { user: String::from("a b"), delta: -5 }]);
     }
 }
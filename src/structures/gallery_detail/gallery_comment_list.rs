@@ -1,10 +1,12 @@
 use std::iter::zip;
 use visdom::Vis;
-use crate::{EhResult, Parser, structures::gallery_detail::GalleryComment};
+use crate::{Context, EhResult, ErrorContext, Parser, structures::gallery_detail::GalleryComment};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryCommentList {
     pub comment_vec: Vec<GalleryComment>,
+    /// Some comments are below the viewing threshold, the page with `hc=1` has them all.
     pub has_more: bool,
 }
 
@@ -36,20 +38,24 @@ impl Parser for GalleryCommentList {
     ///     </div>
     /// </div>
     /// ```
+    /// Or the whole detail page, like the one with every comment shown by `hc=1`.
     fn parse(doc: &str) -> EhResult<Self> {
         let root = Vis::load(doc)?;
+        let cdiv = root.find("#cdiv");
+        let root = if cdiv.is_empty() { root } else { cdiv };
 
         let mut comment_vec = Vec::new();
         let cas = root.find(r#"a[name^=c][name!=cnew]"#);
         let c1s = root.find(".c1");
 
-        for (ca, c1) in zip(cas, c1s) {
+        for (idx, (ca, c1)) in zip(cas, c1s).enumerate() {
             let combine = &format!("{}{}", ca.outer_html(), c1.outer_html());
-            let comment = GalleryComment::parse(&combine)?;
+            let comment = GalleryComment::parse(combine)
+                .with_context(|| ErrorContext::new("GalleryCommentList", &format!("comment_vec[{}]", idx)))?;
             comment_vec.push(comment);
         }
 
-        let show_all = root.find("#chd [rel=nofollow]");
+        let show_all = root.find(r#"#chd a[href*="hc=1"]"#);
         let has_more = !show_all.is_empty();
 
         Ok(GalleryCommentList {
@@ -128,5 +134,74 @@ mod tests {
         "##;
 
         assert_eq!(GalleryCommentList::parse(ele).is_ok(), true);
+        assert!(GalleryCommentList::parse(ele).unwrap().has_more);
+    }
+
+    #[test]
+    fn parse_all_test() {
+        let ele = r##"
+            <html>
+            <body>
+                <div class="gm"><a name="c1"></a><a href="https://e-hentai.org/g/1740161/b90e67b628/">c</a></div>
+                <div id="cdiv" class="gm">
+                    <a name="c3922745"></a>
+                    <div class="c1">
+                        <div class="c2">
+                            <div class="c3">Posted on 24 September 2020, 09:55 by: &nbsp; <a
+                                    href="https://e-hentai.org/uploader/Kalinkawow">Kalinkawow</a></div>
+                            <div class="c4 nosel"></div>
+                            <div class="c5 nosel">Score <span id="comment_score_3922745" style="opacity:1.0">+257</span></div>
+                            <div class="c"></div>
+                        </div>
+                        <div class="c6" id="comment_3922745">猎 妈 人</div>
+                        <div class="c7" id="cvotes_3922745" style="display:none">Base +3, and 50 more...</div>
+                    </div>
+                    <a name="c3922746"></a>
+                    <div class="c1">
+                        <div class="c2">
+                            <div class="c3">Posted on 25 September 2020, 09:55 by: &nbsp; <a
+                                    href="https://e-hentai.org/uploader/xxxx">xxxx</a></div>
+                            <div class="c4 nosel"></div>
+                            <div class="c5 nosel">Score <span id="comment_score_3922746" style="opacity:0.4">-21</span></div>
+                            <div class="c"></div>
+                        </div>
+                        <div class="c6" id="comment_3922746">hidden</div>
+                        <div class="c7" id="cvotes_3922746" style="display:none">Base +3, <span>yyyy -24</span></div>
+                    </div>
+                    <div id="chd">
+                        <p id="postnewcomment">[<a href="#">Post New Comment</a>]</p>
+                    </div>
+                    <a name="cnew"></a>
+                </div>
+            </body>
+            </html>
+        "##;
+
+        let comment_list = GalleryCommentList::parse(ele).unwrap();
+        assert_eq!(comment_list.comment_vec.len(), 2);
+        assert_eq!(comment_list.comment_vec[1].comment_text, "hidden");
+        assert_eq!(comment_list.comment_vec[0].vote_state_opt.as_ref().unwrap().more_count, 50);
+        assert!(!comment_list.has_more);
+    }
+
+    #[test]
+    fn parse_context_test() {
+        let ele = r##"
+            <div id="cdiv" class="gm">
+                <a name="c0"></a>
+                <div class="c1"><div class="c2"><div class="c3">Posted on yesterday by: <a href="#">xxxx</a></div></div></div>
+            </div>
+        "##;
+        let error = GalleryCommentList::parse(ele).unwrap_err();
+
+        let chain = error.context_chain();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].parser, "GalleryCommentList");
+        assert_eq!(chain[0].field, "comment_vec[0]");
+        assert_eq!(chain[1].parser, "GalleryComment");
+        assert_eq!(chain[1].field, "posted_timestamp");
+        assert_eq!(chain[1].snippet_opt.as_deref(), Some("yesterday"));
+        assert!(matches!(error.root_cause(), crate::ParseError::Other(_)));
+        assert!(error.to_string().starts_with("GalleryCommentList.comment_vec[0]: GalleryComment.posted_timestamp `"));
     }
 }
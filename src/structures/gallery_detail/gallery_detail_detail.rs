@@ -1,18 +1,36 @@
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use visdom::Vis;
-use crate::{DOM_NOT_FOUND, EhResult, Parser};
+use crate::{Context, DOM_NOT_FOUND, EhResult, ErrorContext, Parser, REGEX_MATCH_FAILED, datetime::parse_datetime, file_size::{parse_file_size, PATTERN_FILE_SIZE}, structures::{GalleryDetailUrl, GalleryLanguage}};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryDetailDetail {
+    /// As printed, like `2023-02-07 07:33`.
     pub posted: String,
-    pub parent_opt: Option<String>,
-    pub visible: String,
-    pub language: String,
-    pub file_size: String,
+    pub posted_datetime: DateTime<Utc>,
+    pub parent_opt: Option<GalleryDetailUrl>,
+    pub visible: Visibility,
+    pub language: GalleryLanguage,
+    /// In bytes, the site prints it rounded like `225.5 MB`.
+    pub file_size: u64,
     pub pages: u32,
     pub favorite_count: u32,
 }
 
+/// The Visible row, `No (<reason>)` for a gallery hidden from the lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Visibility {
+    Visible,
+    /// `No (Replaced)`, a newer version of the gallery was uploaded.
+    Replaced,
+    /// `No (Expunged)`
+    Expunged,
+    /// `No (<reason>)` with any other reason, empty when the site gives none.
+    Hidden(String),
+}
+
 impl Parser for GalleryDetailDetail {
     /// <table>
     ///     <tr>
@@ -41,67 +59,95 @@ impl Parser for GalleryDetailDetail {
         for gdt1 in gdt1s {
             match gdt1.text().as_str() {
                 "Posted:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
                     posted = Some(gdt2.text());
                 }
                 "Parent:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
 
-                    if let Some(href) = gdt2.get_attribute("href") {
-                        parent_opt = Some(href.to_string());
+                    // `None`, or a link to the parent gallery.
+                    if let Some(href) = Vis::dom(&gdt2).find("a").attr("href") {
+                        parent_opt = Some(GalleryDetailUrl::parse(&href.to_string())
+                            .with_context(|| ErrorContext::new(PARSER, "parent_opt").locator("a[href]").snippet(&href.to_string()))?);
                     }
                 }
                 "Visible:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
-                    visible = Some(gdt2.text());
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
+                    visible = Some(parse_visibility(gdt2.text().trim()));
                 }
                 "Language:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
-                    language = Some(gdt2.text());
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
+                    let gdt2 = gdt2.text();
+                    language = Some(GalleryLanguage::from_detail(&gdt2)
+                        .with_context(|| ErrorContext::new(PARSER, "language").locator(".gdt2").snippet(&gdt2))?);
                 }
                 "File Size:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
-                    file_size = Some(gdt2.text());
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
+                    let gdt2 = gdt2.text();
+                    file_size = Some(parse_file_size(&gdt2)
+                        .with_context(|| ErrorContext::new(PARSER, "file_size").locator(PATTERN_FILE_SIZE).snippet(&gdt2))?);
                 }
                 "Length:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
                     let gdt2 = gdt2.text();
 
                     let regex = Regex::new(PATTERN_PAGES).unwrap();
-                    let captures = regex.captures(&gdt2).unwrap();
+                    let captures = regex.captures(&gdt2)
+                        .ok_or(REGEX_MATCH_FAILED)
+                        .with_context(|| ErrorContext::new(PARSER, "pages").locator(PATTERN_PAGES).snippet(&gdt2))?;
                     pages = Some(captures[1].parse()?);
                 }
                 "Favorited:" => {
-                    let gdt2 = gdt1.next_element_sibling().unwrap();
+                    let gdt2 = gdt1.next_element_sibling().ok_or(DOM_NOT_FOUND)?;
                     let gdt2 = gdt2.text();
 
-                    let regex = Regex::new(PATTERN_FAVORITE_COUNT).unwrap();
-                    let captures = regex.captures(&gdt2).unwrap();
-                    favorite_count = Some(captures[1].parse()?);
+                    favorite_count = Some(match gdt2.as_str() {
+                        "Never" => 0,
+                        "Once" => 1,
+                        _ => {
+                            let regex = Regex::new(PATTERN_FAVORITE_COUNT).unwrap();
+                            let captures = regex.captures(&gdt2)
+                                .ok_or(REGEX_MATCH_FAILED)
+                                .with_context(|| ErrorContext::new(PARSER, "favorite_count").locator(PATTERN_FAVORITE_COUNT).snippet(&gdt2))?;
+                            captures[1].parse()?
+                        }
+                    });
                 }
-                _ => unreachable!()
+                // a row added by the site, the known ones are still read.
+                _ => {}
             }
         }
 
-        if let (
-            Some(posted),
-            Some(visible),
-            Some(language),
-            Some(file_size),
-            Some(pages),
-            Some(favorite_count)
-        ) = (posted, visible, language, file_size, pages, favorite_count) {
-            Ok(GalleryDetailDetail {
-                posted,
-                parent_opt,
-                visible,
-                language,
-                file_size,
-                pages,
-                favorite_count,
-            })
-        } else {
-            Err(DOM_NOT_FOUND)
+        let missing = |field: &str| ErrorContext::new(PARSER, field).locator(".gdt1").snippet(doc);
+
+        let posted = posted.ok_or(DOM_NOT_FOUND).with_context(|| missing("posted"))?;
+        let posted_datetime = parse_datetime(&posted)
+            .with_context(|| ErrorContext::new(PARSER, "posted_datetime").locator(".gdt2").snippet(&posted))?;
+
+        Ok(GalleryDetailDetail {
+            posted,
+            posted_datetime,
+            parent_opt,
+            visible: visible.ok_or(DOM_NOT_FOUND).with_context(|| missing("visible"))?,
+            language: language.ok_or(DOM_NOT_FOUND).with_context(|| missing("language"))?,
+            file_size: file_size.ok_or(DOM_NOT_FOUND).with_context(|| missing("file_size"))?,
+            pages: pages.ok_or(DOM_NOT_FOUND).with_context(|| missing("pages"))?,
+            favorite_count: favorite_count.ok_or(DOM_NOT_FOUND).with_context(|| missing("favorite_count"))?,
+        })
+    }
+}
+
+fn parse_visibility(text: &str) -> Visibility {
+    match text {
+        "Yes" => Visibility::Visible,
+        "No (Replaced)" => Visibility::Replaced,
+        "No (Expunged)" => Visibility::Expunged,
+        _ => {
+            let reason = text.trim_start_matches("No").trim();
+            let reason = reason.strip_prefix('(').and_then(|reason| reason.strip_suffix(')')).unwrap_or(reason);
+            Visibility::Hidden(reason.to_string())
         }
     }
 }
+
+const PARSER: &str = "GalleryDetailDetail";
@@ -1,11 +1,16 @@
 use regex::Regex;
 use visdom::Vis;
 use crate::{EhResult, Parser, REGEX_MATCH_FAILED, ATTRIBUTE_NOT_FOUND};
+use super::{parse_preview_position, parse_preview_title};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryPreviewLarge {
     pub position: u32,
-    pub filename: String,
+    /// `None` when the title has none.
+    pub filename_opt: Option<String>,
+    /// Like `jpg`, `None` for a filename without extension.
+    pub extension_opt: Option<String>,
     pub page_url: String,
     pub image_url: String,
 }
@@ -17,6 +22,11 @@ impl Parser for GalleryPreviewLarge {
     ///     src="https://ehgt.org/5b/f9/5bf9580b3b1f63c508a8af85fc73c0567fe93722-12830376-2458-3497-png_l.jpg"></a>     ///
     /// </div>
     /// ```
+    /// Or
+    /// ```html
+    /// <a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><div title="Page 1: AnMMSC_2_001_1.png"
+    ///     style="width:200px;height:285px;background:transparent url(https://ehgt.org/5b/f9/5bf9580b3b1f63c508a8af85fc73c0567fe93722-12830376-2458-3497-png_l.jpg) 0 0 no-repeat"></div></a>
+    /// ```
     fn parse(doc: &str) -> EhResult<Self> {
         let root = Vis::load(doc)?;
 
@@ -24,29 +34,37 @@ impl Parser for GalleryPreviewLarge {
         let href = a.attr("href").ok_or(ATTRIBUTE_NOT_FOUND)?;
         let page_url = href.to_string();
 
+        // the img in the older markup, the background of the div in the newer one.
         let img = a.children("img");
-        let src = img.attr("src").ok_or(ATTRIBUTE_NOT_FOUND)?;
-        let image_url = src.to_string();
+        let image_url = match img.attr("src") {
+            Some(src) => src.to_string(),
+            None => {
+                let style = a.children("div").attr("style").ok_or(ATTRIBUTE_NOT_FOUND)?.to_string();
+                let regex = Regex::new(PATTERN_BACKGROUND).unwrap();
+                let captures = regex.captures(&style).ok_or(REGEX_MATCH_FAILED)?;
+                String::from(&captures[1])
+            }
+        };
 
-        let title = img.attr("title").ok_or(ATTRIBUTE_NOT_FOUND)?;
-        let title = title.to_string();
-        let regex = Regex::new(PATTERN_FILENAME).unwrap();
-        let captures = regex.captures(&title).ok_or(REGEX_MATCH_FAILED)?;
-        let filename = String::from(&captures[1]);
-
-        let alt = img.attr("alt").ok_or(ATTRIBUTE_NOT_FOUND)?;
-        let position = alt.to_string().parse::<u32>()? - 1;
+        // the img in the older markup, the div in the newer one.
+        let title = a.find("[title]").attr("title").map(|title| title.to_string()).unwrap_or_default();
+        let (position_opt, filename_opt, extension_opt) = parse_preview_title(&title);
+        let position = match position_opt {
+            Some(position) => position,
+            None => parse_preview_position(&page_url)?,
+        };
 
         Ok(GalleryPreviewLarge {
             position,
-            filename,
+            filename_opt,
+            extension_opt,
             page_url,
             image_url,
         })
     }
 }
 
-const PATTERN_FILENAME: &str = r#"Page \d+: ([\w\s]+.[\w]+)"#;
+const PATTERN_BACKGROUND: &str = r#"url\((.+?)\)"#;
 
 #[cfg(test)]
 mod tests {
@@ -62,5 +80,14 @@ mod tests {
         "#;
 
         assert_eq!(GalleryPreviewLarge::parse(ele).is_ok(), true);
+
+        let ele = r#"<a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><div title="Page 1: AnMMSC_2_001_1.png" style="width:200px;height:285px;background:transparent url(https://ehgt.org/5b/f9/5bf9580b3b1f63c508a8af85fc73c0567fe93722-12830376-2458-3497-png_l.jpg) 0 0 no-repeat"></div></a>"#;
+        assert_eq!(GalleryPreviewLarge::parse(ele).unwrap(), GalleryPreviewLarge {
+            position: 0,
+            filename_opt: Some(String::from("AnMMSC_2_001_1.png")),
+            extension_opt: Some(String::from("png")),
+            page_url: String::from("https://e-hentai.org/s/5bf9580b3b/1496103-1"),
+            image_url: String::from("https://ehgt.org/5b/f9/5bf9580b3b1f63c508a8af85fc73c0567fe93722-12830376-2458-3497-png_l.jpg"),
+        });
     }
 }
@@ -1,12 +1,115 @@
+use regex::Regex;
+use visdom::Vis;
+use crate::{EhResult, Parser, REGEX_MATCH_FAILED, ATTRIBUTE_NOT_FOUND};
+use super::{parse_preview_position, parse_preview_title};
+
+/// A normal size preview, a part of a sprite shared by up to 20 pages.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryPreviewMedium {
     pub position: u32,
-    pub filename: String,
+    /// `None` when the title has none.
+    pub filename_opt: Option<String>,
+    /// Like `jpg`, `None` for a filename without extension.
+    pub extension_opt: Option<String>,
     pub page_url: String,
+    /// The sprite, like `https://ehgt.org/m/001496/1496103-00.jpg`.
     pub image_url: String,
-    /// in the medium, the preview is a mosaic of 20 images.
+    /// The preview starts `offset_x` pixels from the left of the sprite.
     pub offset_x: u32,
     pub offset_y: u32,
     pub clip_width: u32,
     pub clip_height: u32,
 }
+
+impl Parser for GalleryPreviewMedium {
+    /// ```html
+    /// <div class="gdtm" style="height:167px">
+    ///     <div style="margin:1px auto 0; width:100px; height:143px; background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat">
+    ///         <a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><img alt="02" title="Page 2: AnMMSC_2_001_2.jpg"
+    ///                 src="https://ehgt.org/g/blank.gif" style="width:100px; height:142px; margin:-1px 0 0 -1px"></a>
+    ///     </div>
+    /// </div>
+    /// ```
+    /// Or
+    /// ```html
+    /// <a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><div title="Page 2: AnMMSC_2_001_2.jpg"
+    ///     style="width:100px;height:142px;background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat"></div></a>
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let root = Vis::load(doc)?;
+
+        let href = root.find("a").attr("href").ok_or(ATTRIBUTE_NOT_FOUND)?;
+        let page_url = href.to_string();
+
+        // the img in the older markup, the div in the newer one.
+        let title = root.find("[title]").attr("title").map(|title| title.to_string()).unwrap_or_default();
+        let (position_opt, filename_opt, extension_opt) = parse_preview_title(&title);
+        let position = match position_opt {
+            Some(position) => position,
+            None => parse_preview_position(&page_url)?,
+        };
+
+        let style = root.find("div[style*=url]").attr("style").ok_or(ATTRIBUTE_NOT_FOUND)?;
+        let style = style.to_string();
+        let regex = Regex::new(PATTERN_SPRITE).unwrap();
+        let captures = regex.captures(&style).ok_or(REGEX_MATCH_FAILED)?;
+        let clip_width = captures[1].parse()?;
+        let clip_height = captures[2].parse()?;
+        let image_url = String::from(&captures[3]);
+        let offset_x = captures[4].parse::<i64>()?.unsigned_abs() as u32;
+        let offset_y = captures[5].parse::<i64>()?.unsigned_abs() as u32;
+
+        Ok(GalleryPreviewMedium {
+            position,
+            filename_opt,
+            extension_opt,
+            page_url,
+            image_url,
+            offset_x,
+            offset_y,
+            clip_width,
+            clip_height,
+        })
+    }
+}
+
+const PATTERN_SPRITE: &str = r#"width:\s*(\d+)px;\s*height:\s*(\d+)px;.*?url\((.+?)\)\s*(-?\d+)(?:px)?\s+(-?\d+)(?:px)?"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let ele = r#"
+            <div class="gdtm" style="height:167px">
+                <div
+                    style="margin:1px auto 0; width:100px; height:143px; background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat">
+                    <a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><img alt="02" title="Page 2: AnMMSC_2_001_2.jpg"
+                            src="https://ehgt.org/g/blank.gif" style="width:100px; height:142px; margin:-1px 0 0 -1px"></a>
+                </div>
+            </div>
+        "#;
+        let expected = GalleryPreviewMedium {
+            position: 1,
+            filename_opt: Some(String::from("AnMMSC_2_001_2.jpg")),
+            extension_opt: Some(String::from("jpg")),
+            page_url: String::from("https://e-hentai.org/s/ad7a3b7014/1496103-2"),
+            image_url: String::from("https://ehgt.org/m/001496/1496103-00.jpg"),
+            offset_x: 100,
+            offset_y: 0,
+            clip_width: 100,
+            clip_height: 143,
+        };
+        assert_eq!(GalleryPreviewMedium::parse(ele).unwrap(), expected);
+
+        let ele = r#"<a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><div title="Page 2: AnMMSC_2_001_2.jpg" style="width:100px;height:143px;background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat"></div></a>"#;
+        assert_eq!(GalleryPreviewMedium::parse(ele).unwrap(), expected);
+
+        let ele = r#"<a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><div style="width:100px;height:143px;background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat"></div></a>"#;
+        let preview = GalleryPreviewMedium::parse(ele).unwrap();
+        assert_eq!(preview.position, 1);
+        assert_eq!(preview.filename_opt, None);
+    }
+}
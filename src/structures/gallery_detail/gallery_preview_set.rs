@@ -1,4 +1,3 @@
-use regex::Regex;
 use visdom::{Vis, types::Elements};
 use crate::{
     EhResult,
@@ -12,6 +11,8 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "preview_vec"))]
 pub enum GalleryPreviewSet {
     Large(Vec<GalleryPreviewLarge>),
     Medium(Vec<GalleryPreviewMedium>),
@@ -55,38 +56,41 @@ impl Parser for GalleryPreviewSet {
     ///     <div class="c"></div>
     /// </div>
     /// ```
+    /// Or
+    /// 3. The newer markup, `gt100` for the medium previews and `gt200` for the large ones.
+    /// ```html
+    /// <div id="gdt" class="gt100">
+    ///     <a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><div title="Page 1: AnMMSC_2_001_1.png"
+    ///         style="width:100px;height:142px;background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -0px 0 no-repeat"></div></a>
+    ///     ...
+    /// </div>
+    /// ```
     fn parse(doc: &str) -> EhResult<Self> {
         let root = Vis::load(doc)?;
-        let gdt = root.find("#gdt > div:first-child");
-        let kind = gdt.attr("class").ok_or(ATTRIBUTE_NOT_FOUND)?;
+        let gdt = root.find("#gdt");
+        if gdt.has_class("gt100") {
+            return Ok(GalleryPreviewSet::Medium(parse_vec(gdt.children("a"))?));
+        }
+        if gdt.has_class("gt200") {
+            return Ok(GalleryPreviewSet::Large(parse_vec(gdt.children("a"))?));
+        }
+
+        let first = gdt.children("div:first-child");
+        let kind = first.attr("class").ok_or(ATTRIBUTE_NOT_FOUND)?;
 
         match kind.to_string().as_str() {
-            r#"gdtl"# => Ok(GalleryPreviewSet::Large(parse_large(&root)?)),
-            r#"gdtm"# => Ok(GalleryPreviewSet::Medium(parse_medium(doc)?)),
-            _ => unreachable!(),
+            r#"gdtl"# => Ok(GalleryPreviewSet::Large(parse_vec(gdt.children(".gdtl"))?)),
+            r#"gdtm"# => Ok(GalleryPreviewSet::Medium(parse_vec(gdt.children(".gdtm"))?)),
+            kind => Err(ParseError::UnrecognizedLayout(format!("preview set, class: {:?}", kind))),
         }
     }
 }
 
-/// ```html
-/// <div id="gdt">
-///     <div class="gdtl" style="height:307px"><a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><img alt="01"
-///                 title="Page 1: AnMMSC_2_001_1.png"
-///                 src="https://ehgt.org/5b/f9/5bf9580b3b1f63c508a8af85fc73c0567fe93722-12830376-2458-3497-png_l.jpg"></a>
-///     </div>
-///     <div class="gdtl" style="height:307px"><a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><img alt="02"
-///                 title="Page 2: AnMMSC_2_001_2.jpg"
-///                 src="https://ehgt.org/ad/7a/ad7a3b7014372ce64193c118b1cfcbcf7ea68ee0-2507700-2458-3497-jpg_l.jpg"></a>
-///     </div>
-///     ...
-///     <div class="c"></div>
-/// </div>
-/// ```
-fn parse_large(root: &Elements) -> Result<Vec<GalleryPreviewLarge>, ParseError> {
+/// Each of `eles` is a preview, a `.gdtl` or `.gdtm` of the older markup, an `a` of the newer one.
+fn parse_vec<T: Parser>(eles: Elements) -> EhResult<Vec<T>> {
     let mut preview_vec = Vec::new();
-    let gdt_larges = root.children(r#".gdtl"#);
-    for gdt_large in gdt_larges {
-        preview_vec.push(GalleryPreviewLarge::parse(&gdt_large.outer_html())?);
+    for ele in eles {
+        preview_vec.push(T::parse(&ele.outer_html())?);
     }
 
     Ok(preview_vec)
@@ -95,59 +99,6 @@ fn parse_large(root: &Elements) -> Result<Vec<GalleryPreviewLarge>, ParseError>
 // const PATTERN_PREVIEW_PAGES: &str = r#"<td[^>]+><a[^>]+>([\d,]+)</a></td><td[^>]+>(?:<a[^>]+>)?&gt;(?:</a>)?</td>"#;
 // const PATTERN_LARGE_PREVIEW: &str = r#"<div class="gdtl".+?<a href="(.+?)"><img alt="([\d,]+)".+?src="(.+?)""#;
 
-/// ```html
-/// <div id="gdt">
-///     <div class="gdtm" style="height:167px">
-///         <div
-///             style="margin:1px auto 0; width:100px; height:143px; background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -0px 0 no-repeat">
-///             <a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><img alt="01" title="Page 1: AnMMSC_2_001_1.png"
-///                     src="https://ehgt.org/g/blank.gif" style="width:100px; height:142px; margin:-1px 0 0 -1px"></a>
-///         </div>
-///     </div>
-///     <div class="gdtm" style="height:167px">
-///         <div
-///             style="margin:1px auto 0; width:100px; height:143px; background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat">
-///             <a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><img alt="02" title="Page 2: AnMMSC_2_001_2.jpg"
-///                     src="https://ehgt.org/g/blank.gif" style="width:100px; height:142px; margin:-1px 0 0 -1px"></a>
-///         </div>
-///     </div>
-///     ...
-///     <div class="c"></div>
-/// </div>
-/// ```
-fn parse_medium(doc: &str) -> Result<Vec<GalleryPreviewMedium>, ParseError> {
-    let mut preview_vec = Vec::new();
-
-    let regex = Regex::new(PATTERN_MEDIUM_PREVIEW).unwrap();
-    for cap in regex.captures_iter(doc) {
-        let clip_width = cap[1].parse()?;
-        let clip_height = cap[2].parse()?;
-        let image_url = String::from(&cap[3]);
-        let offset_x = cap[4].parse()?;
-        let offset_y = 0;
-        let page_url = String::from(&cap[5]);
-        let position = cap[6].parse::<u32>()? - 1;
-        let filename = String::from(&cap[7]);
-
-        preview_vec.push(
-            GalleryPreviewMedium {
-                position,
-                filename,
-                page_url,
-                image_url,
-                offset_x,
-                offset_y,
-                clip_width,
-                clip_height,
-            }
-        );
-    }
-
-    Ok(preview_vec)
-}
-
-const PATTERN_MEDIUM_PREVIEW: &str = r#"<div class="gdtm"[^<>]*><div[^<>]*width:(\d+)[^<>]*height:(\d+)[^<>]*\((.+?)\)[^<>]*-(\d+)px[^<>]*><a[^<>]*href="(.+?)"[^<>]*><img alt="([\d,]+)"[^<>]*title="Page \d+: ([\w\s]+.[\w]+)""#;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,5 +142,41 @@ mod tests {
         "#;
 
         assert_eq!(GalleryPreviewSet::parse(ele).is_ok(), true);
+        match GalleryPreviewSet::parse(ele).unwrap() {
+            GalleryPreviewSet::Medium(preview_vec) => {
+                assert_eq!(preview_vec.len(), 2);
+                assert_eq!(preview_vec[1].offset_x, 100);
+            }
+            preview_set => panic!("{:?}", preview_set),
+        }
+
+        let ele = r#"
+            <div id="gdt" class="gt100">
+                <a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><div title="Page 1: AnMMSC_2_001_1.png"
+                    style="width:100px;height:142px;background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -0px 0 no-repeat"></div></a>
+                <a href="https://e-hentai.org/s/ad7a3b7014/1496103-2"><div title="Page 2: AnMMSC_2_001_2.jpg"
+                    style="width:100px;height:142px;background:transparent url(https://ehgt.org/m/001496/1496103-00.jpg) -100px 0 no-repeat"></div></a>
+            </div>
+        "#;
+        match GalleryPreviewSet::parse(ele).unwrap() {
+            GalleryPreviewSet::Medium(preview_vec) => {
+                assert_eq!(preview_vec.len(), 2);
+                assert_eq!(preview_vec[1].position, 1);
+                assert_eq!(preview_vec[1].offset_x, 100);
+                assert_eq!(preview_vec[1].image_url, "https://ehgt.org/m/001496/1496103-00.jpg");
+            }
+            preview_set => panic!("{:?}", preview_set),
+        }
+
+        let ele = r#"
+            <div id="gdt" class="gt200">
+                <a href="https://e-hentai.org/s/5bf9580b3b/1496103-1"><div title="Page 1: AnMMSC_2_001_1.png"
+                    style="width:200px;height:285px;background:transparent url(https://ehgt.org/5b/f9/5bf9580b3b1f63c508a8af85fc73c0567fe93722-12830376-2458-3497-png_l.jpg) 0 0 no-repeat"></div></a>
+            </div>
+        "#;
+        assert!(matches!(GalleryPreviewSet::parse(ele).unwrap(), GalleryPreviewSet::Large(preview_vec) if preview_vec.len() == 1));
+
+        let ele = r#"<div id="gdt"><div class="gdtx"></div></div>"#;
+        assert!(matches!(GalleryPreviewSet::parse(ele), Err(ParseError::UnrecognizedLayout(_))));
     }
 }
@@ -1,14 +1,16 @@
 mod gallery_detail_detail;
 mod gallery_comment_list;
 mod gallery_comment;
+mod comment_node;
 mod gallery_preview_large;
 mod gallery_preview_medium;
 mod gallery_preview_set;
 
 pub use {
-    gallery_detail_detail::GalleryDetailDetail,
+    gallery_detail_detail::{GalleryDetailDetail, Visibility},
     gallery_comment_list::GalleryCommentList,
-    gallery_comment::GalleryComment,
+    gallery_comment::{GalleryComment, GalleryCommentVote, GalleryCommentVoteState},
+    comment_node::{CommentLinkTarget, CommentNode},
     gallery_preview_set::GalleryPreviewSet,
     gallery_preview_medium::GalleryPreviewMedium,
     gallery_preview_large::GalleryPreviewLarge,
@@ -18,11 +20,13 @@ use std::collections::HashMap;
 use regex::Regex;
 use visdom::Vis;
 use crate::{
+    Context,
     EhResult,
-    ParseError,
+    ErrorContext,
     Parser,
     ATTRIBUTE_NOT_FOUND,
     REGEX_MATCH_FAILED,
+    page_kind::pre_check,
     unescape::unescape,
     structures::{
         Category,
@@ -34,6 +38,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryDetail {
     pub gid: u64,
     pub token: String,
@@ -63,43 +68,45 @@ pub struct GalleryDetail {
 
 impl Parser for GalleryDetail {
     fn parse(doc: &str) -> EhResult<Self> {
-        if doc.contains(OFFENSIVE_STRING) {
-            return Err(ParseError::FromServer(String::from("if you choose to ignore this warning, you lose all rights to complain about it in the future.")));
-        }
-
-        if doc.contains(PINING_STRING) {
-            return Err(ParseError::FromServer(String::from("this gallery_list is pining for the fjords.")));
-        }
-
-        let regex = Regex::new(PATTERN_ERROR).unwrap();
-        if let Some(cap) = regex.captures(doc) {
-            return Err(ParseError::FromServer(String::from(&cap[1])));
-        }
+        pre_check(doc)?;
 
         let regex = Regex::new(PATTERN_DETAIL).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
-        let gid = captures[1].parse()?;
-        let api_uid = captures[5].parse()?;
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "gid").locator(PATTERN_DETAIL))?;
+        let gid = captures[1].parse::<u64>()
+            .context(ErrorContext::new(PARSER, "gid").locator(PATTERN_DETAIL))?;
+        let api_uid = captures[5].parse::<u64>()
+            .context(ErrorContext::new(PARSER, "api_uid").locator(PATTERN_DETAIL))?;
         let token = String::from(&captures[3]);
         let api_key = String::from(&captures[7]);
 
         let regex = Regex::new(PATTERN_TORRENT).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "torrent_url").locator(PATTERN_TORRENT))?;
         let torrent_url = String::from(unescape(&captures[1]));
-        let torrent_count = captures[2].parse()?;
+        let torrent_count = captures[2].parse::<u32>()
+            .context(ErrorContext::new(PARSER, "torrent_count").locator(PATTERN_TORRENT))?;
 
         let regex = Regex::new(PATTERN_ARCHIVE).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "archive_url").locator(PATTERN_ARCHIVE))?;
         let archive_url = String::from(unescape(&captures[1]));
 
         let root = Vis::load(doc)?;
         let gm = root.find(".gm:not(#cdiv)");
 
         let cover = gm.find("#gd1 div:first-child");
-        let style = cover.attr("style").ok_or(ATTRIBUTE_NOT_FOUND)?;
+        let style = cover.attr("style")
+            .ok_or(ATTRIBUTE_NOT_FOUND)
+            .with_context(|| ErrorContext::new(PARSER, "thumb").locator("#gd1 div:first-child").snippet(&gm.find("#gd1").outer_html()))?;
         let style = style.to_string();
         let regex = Regex::new(PATTERN_COVER).unwrap();
-        let captures = regex.captures(&style).ok_or(REGEX_MATCH_FAILED)?;
+        let captures = regex.captures(&style)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| ErrorContext::new(PARSER, "thumb").locator(PATTERN_COVER).snippet(&style))?;
         let thumb = String::from(&captures[3]);
 
         let gn = gm.find("#gn");
@@ -116,18 +123,23 @@ impl Parser for GalleryDetail {
         let uploader = gdn.text();
 
         let gdd = gm.find("#gdd");
-        let detail = GalleryDetailDetail::parse(&gdd.html())?;
+        let detail = GalleryDetailDetail::parse(&gdd.html())
+            .context(ErrorContext::new(PARSER, "detail").locator("#gdd"))?;
 
         let rat = gm.find("#rating_count");
-        let rating_count = rat.text().parse()?;
+        let rating_count = rat.text().parse::<u32>()
+            .with_context(|| ErrorContext::new(PARSER, "rating_count").locator("#rating_count").snippet(&rat.outer_html()))?;
 
         let label = gm.find("#rating_label");
         let label_text = label.text();
         let mut rating_opt: Option<f32> = None;
         if label_text != "Not Yet Rated" {
             let regex = Regex::new(PATTERN_RATING).unwrap();
-            let captures = regex.captures(&label_text).ok_or(REGEX_MATCH_FAILED)?;
-            rating_opt = Some(captures[1].parse()?);
+            let captures = regex.captures(&label_text)
+                .ok_or(REGEX_MATCH_FAILED)
+                .with_context(|| ErrorContext::new(PARSER, "rating_opt").locator(PATTERN_RATING).snippet(&label_text))?;
+            rating_opt = Some(captures[1].parse()
+                .with_context(|| ErrorContext::new(PARSER, "rating_opt").locator(PATTERN_RATING).snippet(&label_text))?);
         }
 
         let gdf = gm.find("#gdf");
@@ -136,8 +148,12 @@ impl Parser for GalleryDetail {
 
         let (favorite_slot_opt, favorite_name_opt) = if is_favorited {
             let i = gdf.find(".i");
-            let style = i.attr("style").ok_or(ATTRIBUTE_NOT_FOUND)?;
-            let favorite_slot = FavoriteSlot::parse(&style.to_string())?.value;
+            let style = i.attr("style")
+                .ok_or(ATTRIBUTE_NOT_FOUND)
+                .with_context(|| ErrorContext::new(PARSER, "favorite_slot_opt").locator("#gdf .i").snippet(&gdf.outer_html()))?;
+            let favorite_slot = FavoriteSlot::parse(&style.to_string())
+                .context(ErrorContext::new(PARSER, "favorite_slot_opt").locator("#gdf .i"))?
+                .value;
 
             (Some(favorite_slot), Some(favorite_link.text()))
         } else {
@@ -154,9 +170,11 @@ impl Parser for GalleryDetail {
             let mut newer_version_map = HashMap::new();
             let hrefs = gnd.find("a");
             for (idx, href) in hrefs.into_iter().enumerate() {
-                let href = href.get_attribute("href").ok_or(ATTRIBUTE_NOT_FOUND)?;
-                let detail_url = GalleryDetailUrl::parse(&href.to_string())?;
-                newer_version_map.insert(date_vec[idx].clone(), detail_url);
+                let context = || ErrorContext::new(PARSER, "newer_version_map_opt").locator("#gnd a").snippet(&gnd.outer_html());
+                let href = href.get_attribute("href").ok_or(ATTRIBUTE_NOT_FOUND).with_context(context)?;
+                let detail_url = GalleryDetailUrl::parse(&href.to_string()).with_context(context)?;
+                let date = date_vec.get(idx).ok_or(REGEX_MATCH_FAILED).with_context(context)?;
+                newer_version_map.insert(date.clone(), detail_url);
             }
             Some(newer_version_map)
         } else {
@@ -164,27 +182,27 @@ impl Parser for GalleryDetail {
         };
 
         let c_div = root.find("#cdiv");
-        let comment_list = GalleryCommentList::parse(&c_div.outer_html())?;
+        let comment_list = GalleryCommentList::parse(&c_div.outer_html())
+            .context(ErrorContext::new(PARSER, "comment_list").locator("#cdiv"))?;
 
         let last_page = root.find(".ptt td:nth-last-child(2) > a");
-        let preview_pages = last_page.text().parse()?;
+        let preview_pages = last_page.text().parse::<u32>()
+            .with_context(|| ErrorContext::new(PARSER, "preview_pages").locator(".ptt td:nth-last-child(2) > a").snippet(&root.find(".ptt").outer_html()))?;
 
         let first_page = root.find(".ptt td:nth-child(2) > a");
-        let href = first_page.attr("href").ok_or(ATTRIBUTE_NOT_FOUND)?;
+        let href = first_page.attr("href")
+            .ok_or(ATTRIBUTE_NOT_FOUND)
+            .with_context(|| ErrorContext::new(PARSER, "url").locator(".ptt td:nth-child(2) > a").snippet(&root.find(".ptt").outer_html()))?;
         let url = href.to_string();
 
-        let gdo4 = root.find("#gdo4");
-        let can_click = gdo4.children("[onclick]");
-
+        // the kind is told by the previews themselves, the `#gdo4` switch may be translated.
         let gdt = root.find("#gdt");
-        let preview_set = match can_click.text().as_str() {
-            "Large" => GalleryPreviewSet::parse(&gdt.outer_html())?,
-            "Normal" => GalleryPreviewSet::parse(&gdt.outer_html())?,
-            _ => unreachable!(),
-        };
+        let preview_set = GalleryPreviewSet::parse(&gdt.outer_html())
+            .context(ErrorContext::new(PARSER, "preview_set").locator("#gdt"))?;
 
         let tag_list = root.find("#taglist");
-        let tag_group_list = GalleryTagGroupList::parse(&tag_list.outer_html())?;
+        let tag_group_list = GalleryTagGroupList::parse(&tag_list.outer_html())
+            .context(ErrorContext::new(PARSER, "tag_group_vec").locator("#taglist"))?;
         let tag_group_vec = tag_group_list.group_vec;
 
         Ok(GalleryDetail {
@@ -216,20 +234,46 @@ impl Parser for GalleryDetail {
     }
 }
 
-const OFFENSIVE_STRING: &str = "<p>(And if you choose to ignore this warning, you lose all rights to complain about it in the future.)</p>";
-const PINING_STRING: &str = "<p>This gallery_list is pining for the fjords.</p>";
-const PATTERN_ERROR: &str = "<div class=\"d\">\n<p>([^<]+)</p>";
+/// The title of a preview, `Page 12: [group] 001 - cover.tar.png`.
+/// Gives the position from 0, the filename and its extension, `None` for the parts missing.
+fn parse_preview_title(title: &str) -> (Option<u32>, Option<String>, Option<String>) {
+    let regex = Regex::new(PATTERN_PREVIEW_TITLE).unwrap();
+    let Some(captures) = regex.captures(title) else {
+        return (None, None, None);
+    };
+
+    let position_opt = captures[1].parse::<u32>().ok().and_then(|page| page.checked_sub(1));
+    let filename = captures[2].trim();
+    let filename_opt = Some(String::from(filename)).filter(|filename| !filename.is_empty());
+    let extension_opt = filename.rsplit_once('.')
+        .map(|(stem, extension)| (stem, extension.trim()))
+        .filter(|(stem, extension)| !stem.is_empty() && !extension.is_empty() && extension.chars().all(char::is_alphanumeric))
+        .map(|(_, extension)| String::from(extension));
+
+    (position_opt, filename_opt, extension_opt)
+}
+
+/// The position from 0 in the page url, `https://e-hentai.org/s/5bf9580b3b/1496103-12`.
+fn parse_preview_position(page_url: &str) -> EhResult<u32> {
+    let regex = Regex::new(PATTERN_PREVIEW_PAGE_URL).unwrap();
+    let captures = regex.captures(page_url).ok_or(REGEX_MATCH_FAILED)?;
+    captures[1].parse::<u32>()?.checked_sub(1).ok_or(REGEX_MATCH_FAILED)
+}
+
+const PARSER: &str = "GalleryDetail";
 const PATTERN_DETAIL: &str = "var gid = (\\d+);\\s*?(\n|\r|\r\n)?\\s*?var token = \"([a-f0-9]+)\";\\s*?(\n|\r|\r\n)?\\s*?var apiuid = ([\\-\\d]+);\\s*?(\n|\r|\r\n)?\\s*?var apikey = \"([a-f0-9]+)\";";
 const PATTERN_TORRENT: &str = r#"<a[^<>]*onclick="return popUp\('([^']+)'[^)]+\)">Torrent Download[^<]+(\d+)[^<]+</a"#;
 const PATTERN_ARCHIVE: &str = r#"<a[^<>]*onclick="return popUp\('([^']+)'[^)]+\)">Archive Download</a>"#;
 const PATTERN_RATING: &str = r#"[+-]?([0-9]*[.]?[0-9]+)"#;
 const PATTERN_NEWER_DATE: &str = ", added (.+?)<br />";
 const PATTERN_COVER: &str = r#"width:(\d+)px; height:(\d+)px.+?url\((.+?)\)"#;
+const PATTERN_PREVIEW_TITLE: &str = r#"(?s)^\s*Page\s+(\d+):\s*(.*)$"#;
+const PATTERN_PREVIEW_PAGE_URL: &str = r#"-(\d+)/?(?:\?.*)?$"#;
 // const PATTERN_PAGES: &str = r#"<tr><td[^<>]*>Length:</td><td[^<>]*>([\d,]+) pages</td></tr>"#;
 
 #[cfg(test)]
 mod tests {
-    use crate::test_helper::read_test_file;
+    use crate::{test_helper::read_test_file, structures::{GalleryLanguage, Language, Origin}};
     use super::*;
 
     #[test]
@@ -238,6 +282,23 @@ mod tests {
         assert_eq!(GalleryDetail::parse(&doc).is_ok(), true);
     }
 
+    #[test]
+    fn parse_preview_title_test() {
+        let title = |title: &str| parse_preview_title(title);
+        let some = |str: &str| Some(String::from(str));
+
+        assert_eq!(title("Page 1: AnMMSC_2_001_1.png"), (Some(0), some("AnMMSC_2_001_1.png"), some("png")));
+        assert_eq!(title("Page 12: [group] 001 - cover (1).jpeg"), (Some(11), some("[group] 001 - cover (1).jpeg"), some("jpeg")));
+        assert_eq!(title("Page 3: 第3話 表紙.v2.webp"), (Some(2), some("第3話 表紙.v2.webp"), some("webp")));
+        assert_eq!(title("Page 4: no_extension"), (Some(3), some("no_extension"), None));
+        assert_eq!(title("Page 5: .hidden"), (Some(4), some(".hidden"), None));
+        assert_eq!(title("Page 6: "), (Some(5), None, None));
+        assert_eq!(title("cover.jpg"), (None, None, None));
+
+        assert_eq!(parse_preview_position("https://e-hentai.org/s/5bf9580b3b/1496103-12").unwrap(), 11);
+        assert!(parse_preview_position("https://e-hentai.org/s/5bf9580b3b/").is_err());
+    }
+
     #[test]
     fn parse_detail_test() {
         let table = r#"
@@ -274,5 +335,30 @@ mod tests {
         "#;
 
         assert_eq!(GalleryDetailDetail::parse(table).is_ok(), true);
+
+        let detail = GalleryDetailDetail::parse(table).unwrap();
+        assert_eq!(detail.parent_opt, None);
+        assert_eq!(detail.visible, Visibility::Visible);
+        assert_eq!(detail.language, GalleryLanguage::new(Language::Japanese, Origin::Original));
+        assert_eq!(detail.file_size, 236453888);
+
+        let table = r#"
+        <table>
+            <tr><td class="gdt1">Posted:</td><td class="gdt2">2023-02-07 07:33</td></tr>
+            <tr><td class="gdt1">Parent:</td><td class="gdt2"><a href="https://e-hentai.org/g/2455980/0123456789/">2455980</a></td></tr>
+            <tr><td class="gdt1">Visible:</td><td class="gdt2">No (Replaced)</td></tr>
+            <tr><td class="gdt1">Language:</td><td class="gdt2">Chinese &nbsp;<span class="halp" title="This gallery has been translated from the original language text.">TR</span></td></tr>
+            <tr><td class="gdt1">File Size:</td><td class="gdt2">1.5 GiB</td></tr>
+            <tr><td class="gdt1">Length:</td><td class="gdt2">75 pages</td></tr>
+            <tr><td class="gdt1">Favorited:</td><td class="gdt2" id="favcount">Once</td></tr>
+        </table>
+        "#;
+
+        let detail = GalleryDetailDetail::parse(table).unwrap();
+        assert_eq!(detail.parent_opt, Some(GalleryDetailUrl { gid: 2455980, token: String::from("0123456789") }));
+        assert_eq!(detail.visible, Visibility::Replaced);
+        assert_eq!(detail.language, GalleryLanguage::new(Language::Chinese, Origin::Translated));
+        assert_eq!(detail.file_size, 1610612736);
+        assert_eq!(detail.favorite_count, 1);
     }
 }
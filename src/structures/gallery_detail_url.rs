@@ -2,6 +2,7 @@ use regex::Regex;
 use crate::{eh_url, EhResult, Parser, const_concat, REGEX_MATCH_FAILED};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryDetailUrl {
     pub gid: u64,
     pub token: String,
@@ -0,0 +1,184 @@
+use regex::Regex;
+use crate::{Context, EhResult, ErrorContext, ParseError, Parser, REGEX_MATCH_FAILED, Site, file_size::parse_file_size, server_error::is_image_limit_url, unescape::unescape};
+use crate::structures::{GalleryMultiPageViewer, GalleryPageMpvApi, GalleryPageUrl};
+
+/// A page of a gallery as the reader shows it, from [`crate::structures::GalleryPage`],
+/// [`crate::structures::GalleryPageApi`] or [`GalleryImage::from_mpv`].
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryImage {
+    pub page_url: GalleryPageUrl,
+    pub page_count: u32,
+    pub image_url: String,
+    /// Like `002.jpg`.
+    pub filename: String,
+    pub width: u32,
+    pub height: u32,
+    /// In bytes, of the image shown, not of the original.
+    pub file_size: u64,
+    /// `None` when the image shown is the original.
+    pub origin_image_url_opt: Option<String>,
+    pub skip_hath_key: String,
+    /// `None` on the first page.
+    pub prev_page_url_opt: Option<GalleryPageUrl>,
+    /// `None` on the last page.
+    pub next_page_url_opt: Option<GalleryPageUrl>,
+}
+
+impl GalleryImage {
+    /// Reads the parts the page and the `showpage` answer share, `page` starts from 0.
+    ///
+    /// ```html
+    /// <div class="sn"><a onclick="return load_image(1, '35142216f7')" href="https://e-hentai.org/s/35142216f7/2455981-1">...</a><a
+    ///         id="prev" onclick="return load_image(1, '35142216f7')" href="https://e-hentai.org/s/35142216f7/2455981-1">...</a>
+    ///     <div><span>2</span> / <span>30</span></div><a id="next" onclick="return load_image(3, 'c3a1b0d9e8')"
+    ///         href="https://e-hentai.org/s/c3a1b0d9e8/2455981-3">...</a></div>
+    /// <div>002.jpg :: 1280 x 1810 :: 354.6 KiB</div>
+    /// <a onclick="return load_image(3, 'c3a1b0d9e8')" href="https://e-hentai.org/s/c3a1b0d9e8/2455981-3"><img id="img"
+    ///         src="https://abcdefg.hath.network/h/.../002.jpg" style="height:1810px;width:1280px" onerror="this.onerror=null; nl('42377-430136')"></a>
+    /// <a href="#" id="loadfail" onclick="return nl('42377-430136')">Reload broken image</a>
+    /// <a href="https://e-hentai.org/fullimg/2455981/2/3nk7sa2fyfj/002.jpg">Download original 2400 x 3394 1.75 MiB source</a>
+    /// ```
+    pub(crate) fn parse_page(doc: &str, page: u32, p_token: String) -> EhResult<Self> {
+        let context = |field: &str, locator: &str| ErrorContext::new(PARSER, field).locator(locator);
+
+        let regex = Regex::new(PATTERN_PAGE_COUNT).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("page_count", PATTERN_PAGE_COUNT))?;
+        let page_count = captures[2].parse()
+            .with_context(|| context("page_count", PATTERN_PAGE_COUNT))?;
+
+        let neighbour_opt = |pattern: &str| -> Option<GalleryPageUrl> {
+            let regex = Regex::new(pattern).unwrap();
+            let captures = regex.captures(doc)?;
+            GalleryPageUrl::parse(&captures[1]).ok().filter(|page_url| page_url.page != page)
+        };
+        let prev_page_url_opt = neighbour_opt(PATTERN_PREV);
+        let next_page_url_opt = neighbour_opt(PATTERN_NEXT);
+
+        // the neighbours link back to the current page when there is none.
+        let regex = Regex::new(PATTERN_NEIGHBOUR).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("page_url", PATTERN_NEIGHBOUR))?;
+        let gid = GalleryPageUrl::parse(&captures[1])
+            .with_context(|| context("page_url", PATTERN_NEIGHBOUR))?
+            .gid;
+        let page_url = GalleryPageUrl { gid, p_token, page };
+
+        let regex = Regex::new(PATTERN_INFO).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("filename", PATTERN_INFO))?;
+        let filename = String::from(unescape(captures[1].trim()));
+        let width = captures[2].parse()
+            .with_context(|| context("width", PATTERN_INFO))?;
+        let height = captures[3].parse()
+            .with_context(|| context("height", PATTERN_INFO))?;
+        let file_size = parse_file_size(&captures[4])
+            .with_context(|| context("file_size", PATTERN_INFO).snippet(&captures[4]))?;
+
+        let regex = Regex::new(PATTERN_IMAGE_URL).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("image_url", PATTERN_IMAGE_URL))?;
+        let image_url = String::from(unescape(&captures[1]));
+        if is_image_limit_url(&image_url) {
+            return Err(ParseError::ImageLimitExceeded);
+        }
+
+        let regex = Regex::new(PATTERN_SKIP_HATH_KEY).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .with_context(|| context("skip_hath_key", PATTERN_SKIP_HATH_KEY))?;
+        let skip_hath_key = String::from(&captures[1]);
+
+        let regex = Regex::new(PATTERN_ORIGIN_IMAGE_URL).unwrap();
+        let origin_image_url_opt = regex.captures(doc)
+            .map(|captures| format!("{}{}{}", &captures[1], "fullimg", unescape(&captures[2])));
+
+        Ok(GalleryImage {
+            page_url,
+            page_count,
+            image_url,
+            filename,
+            width,
+            height,
+            file_size,
+            origin_image_url_opt,
+            skip_hath_key,
+            prev_page_url_opt,
+            next_page_url_opt,
+        })
+    }
+
+    /// Joins the `imagedispatch` answer with the page list of the viewer, `page` starts from 0.
+    pub fn from_mpv(mpv: &GalleryMultiPageViewer, page: u32, api: GalleryPageMpvApi, site: Site) -> EhResult<Self> {
+        let page_url_opt = |page: u32| mpv.image_vec.get(page as usize).map(|image| GalleryPageUrl {
+            gid: mpv.gid,
+            p_token: image.p_token.clone(),
+            page,
+        });
+
+        if is_image_limit_url(&api.image_url) {
+            return Err(ParseError::ImageLimitExceeded);
+        }
+
+        let image = mpv.image_vec.get(page as usize)
+            .ok_or(ParseError::OutOfRange)
+            .with_context(|| ErrorContext::new(PARSER, "page_url").locator(&format!("image_vec[{}]", page)))?;
+        let origin_image_url_opt = Some(api.origin_image_url(site)).filter(|_| !api.origin_image_path.is_empty());
+
+        Ok(GalleryImage {
+            page_url: GalleryPageUrl { gid: mpv.gid, p_token: image.p_token.clone(), page },
+            page_count: mpv.page_count,
+            image_url: api.image_url,
+            filename: image.name.clone(),
+            width: api.width,
+            height: api.height,
+            file_size: api.file_size,
+            origin_image_url_opt,
+            skip_hath_key: api.skip_hath_key,
+            prev_page_url_opt: page.checked_sub(1).and_then(page_url_opt),
+            next_page_url_opt: page_url_opt(page + 1),
+        })
+    }
+}
+
+const PARSER: &str = "GalleryImage";
+const PATTERN_PAGE_COUNT: &str = r#"<span>(\d+)</span>\s*/\s*<span>(\d+)</span>"#;
+const PATTERN_PREV: &str = r#"<a\s+id="prev"[^>]*?href="([^"]+)""#;
+const PATTERN_NEXT: &str = r#"<a\s+id="next"[^>]*?href="([^"]+)""#;
+const PATTERN_NEIGHBOUR: &str = r#"<a\s+id="(?:prev|next)"[^>]*?href="([^"]+)""#;
+const PATTERN_INFO: &str = r#"<div>([^<>]+?)\s*::\s*(\d+)\s*x\s*(\d+)\s*::\s*([^<>]+?)\s*</div>"#;
+const PATTERN_IMAGE_URL: &str = r#"<img[^>]*?\bid="img"[^>]*?\bsrc="([^"]+)""#;
+const PATTERN_SKIP_HATH_KEY: &str = r#"onclick="return nl\('([^\)]+)'\)"#;
+const PATTERN_ORIGIN_IMAGE_URL: &str = r#"<a\s+href="([^"]+)fullimg([^"]+)">"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_mpv_test() {
+        let mpv = GalleryMultiPageViewer::parse(r#"
+            var gid = 2455981;
+            var mpvkey = "0a2b4c6d8e";
+            var pagecount = 2;
+            var imagelist = [{"n":"001.jpg","k":"35142216f7","t":"(https:\/\/ehgt.org\/m\/002455\/2455981-00.jpg) -0px 0"},
+                {"n":"002.jpg","k":"8a3b1f9e22","t":"(https:\/\/ehgt.org\/m\/002455\/2455981-00.jpg) -100px 0"}];
+        "#).unwrap();
+        let api = GalleryPageMpvApi::parse(r#"{"d":"1280 x 1810 :: 354.6 KiB","lf":"fullimg\/2455981\/2\/3nk7sa2fyfj\/002.jpg","xres":"1280","yres":"1810","i":"https:\/\/abcdefg.hath.network\/h\/002.jpg","s":"42377"}"#).unwrap();
+
+        let image = GalleryImage::from_mpv(&mpv, 1, api, Site::E).unwrap();
+        assert_eq!(image.page_url, GalleryPageUrl { gid: 2455981, p_token: String::from("8a3b1f9e22"), page: 1 });
+        assert_eq!(image.filename, "002.jpg");
+        assert_eq!(image.origin_image_url_opt.as_deref(), Some("https://e-hentai.org/fullimg/2455981/2/3nk7sa2fyfj/002.jpg"));
+        assert_eq!(image.prev_page_url_opt.map(|page_url| page_url.p_token), Some(String::from("35142216f7")));
+        assert_eq!(image.next_page_url_opt, None);
+
+        let api = GalleryPageMpvApi::parse(r#"{"d":"1 KiB","lf":"","xres":1,"yres":1,"i":"","s":1}"#).unwrap();
+        assert!(GalleryImage::from_mpv(&mpv, 2, api, Site::E).is_err());
+    }
+}
@@ -4,35 +4,43 @@
 //! 4. Extended
 //! 5. Thumbnail
 
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use visdom::{Vis, types::Elements};
 use crate::{
+    Context,
     EhResult,
+    ErrorContext,
     Parser,
     REGEX_MATCH_FAILED,
     ATTRIBUTE_NOT_FOUND,
+    datetime::parse_datetime,
     structures::{
         Thumb,
         Rating,
         Category,
         FavoriteSlot,
         GalleryDetailUrl,
+        Language,
     },
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryInfoMinimal {
     pub gid: u64,
     pub token: String,
     pub title: String,
     pub thumb: Thumb,
     pub category: u32,
+    /// As printed, like `2023-02-07 07:33`.
     pub posted: String,
+    pub posted_datetime: DateTime<Utc>,
     /// Minimal MinimalPlus Compact Extended
     pub uploader: String,
     pub rating: f32,
     pub pages: u32,
-    pub simple_language_opt: Option<String>,
+    pub simple_language_opt: Option<Language>,
     pub is_favorited: bool,
     pub favorite_slot_opt: Option<u32>,
     pub favorite_name_opt: Option<String>,
@@ -46,7 +54,7 @@ impl Parser for GalleryInfoMinimal {
         let pages = parse_pages_1_2_3(&root)?;
         let thumb = parse_thumb_1_2_3(&root)?;
         let rating = parse_rating_1_2_3(&root)?;
-        let posted = parse_posted(&root)?;
+        let (posted, posted_datetime) = parse_posted(&root)?;
         let is_favorited = parse_is_favorited(&root)?;
         let favorite_slot_opt = parse_favorite_slot_opt(&root)?;
         let favorite_name_opt = parse_favorite_name_opt(&root)?;
@@ -61,6 +69,7 @@ impl Parser for GalleryInfoMinimal {
             thumb,
             category,
             posted,
+            posted_datetime,
             uploader,
             rating,
             pages,
@@ -73,17 +82,20 @@ impl Parser for GalleryInfoMinimal {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryInfoMinimalPlus {
     pub gid: u64,
     pub token: String,
     pub title: String,
     pub thumb: Thumb,
     pub category: u32,
+    /// As printed, like `2023-02-07 07:33`.
     pub posted: String,
+    pub posted_datetime: DateTime<Utc>,
     pub uploader: String,
     pub rating: f32,
     pub pages: u32,
-    pub simple_language_opt: Option<String>,
+    pub simple_language_opt: Option<Language>,
     pub is_favorited: bool,
     pub favorite_slot_opt: Option<u32>,
     pub favorite_name_opt: Option<String>,
@@ -91,7 +103,22 @@ pub struct GalleryInfoMinimalPlus {
 
 impl From<GalleryInfoMinimal> for GalleryInfoMinimalPlus {
     fn from(value: GalleryInfoMinimal) -> Self {
-        unsafe { std::mem::transmute(value) }
+        GalleryInfoMinimalPlus {
+            gid: value.gid,
+            token: value.token,
+            title: value.title,
+            thumb: value.thumb,
+            category: value.category,
+            posted: value.posted,
+            posted_datetime: value.posted_datetime,
+            uploader: value.uploader,
+            rating: value.rating,
+            pages: value.pages,
+            simple_language_opt: value.simple_language_opt,
+            is_favorited: value.is_favorited,
+            favorite_slot_opt: value.favorite_slot_opt,
+            favorite_name_opt: value.favorite_name_opt,
+        }
     }
 }
 
@@ -102,20 +129,23 @@ impl Parser for GalleryInfoMinimalPlus {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryInfoCompact {
     pub gid: u64,
     pub token: String,
     pub title: String,
     pub thumb: Thumb,
     pub category: u32,
+    /// As printed, like `2023-02-07 07:33`.
     pub posted: String,
+    pub posted_datetime: DateTime<Utc>,
     /// Minimal MinimalPlus Compact Extended
     pub uploader: String,
     pub rating: f32,
     /// Compact Extended
     pub simple_tag_vec: Vec<String>,
     pub pages: u32,
-    pub simple_language_opt: Option<String>,
+    pub simple_language_opt: Option<Language>,
     pub is_favorited: bool,
     pub favorite_slot_opt: Option<u32>,
     pub favorite_name_opt: Option<String>,
@@ -129,7 +159,7 @@ impl Parser for GalleryInfoCompact {
         let pages = parse_pages_1_2_3(&root)?;
         let thumb = parse_thumb_1_2_3(&root)?;
         let rating = parse_rating_1_2_3(&root)?;
-        let posted = parse_posted(&root)?;
+        let (posted, posted_datetime) = parse_posted(&root)?;
         let is_favorited = parse_is_favorited(&root)?;
         let favorite_slot_opt = parse_favorite_slot_opt(&root)?;
         let favorite_name_opt = parse_favorite_name_opt(&root)?;
@@ -145,6 +175,7 @@ impl Parser for GalleryInfoCompact {
             thumb,
             category,
             posted,
+            posted_datetime,
             uploader,
             rating,
             simple_tag_vec,
@@ -158,20 +189,23 @@ impl Parser for GalleryInfoCompact {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryInfoExtended {
     pub gid: u64,
     pub token: String,
     pub title: String,
     pub thumb: Thumb,
     pub category: u32,
+    /// As printed, like `2023-02-07 07:33`.
     pub posted: String,
+    pub posted_datetime: DateTime<Utc>,
     /// Minimal MinimalPlus Compact Extended
     pub uploader: String,
     pub rating: f32,
     /// Compact Extended
     pub simple_tag_vec: Vec<String>,
     pub pages: u32,
-    pub simple_language_opt: Option<String>,
+    pub simple_language_opt: Option<Language>,
     pub is_favorited: bool,
     pub favorite_slot_opt: Option<u32>,
     pub favorite_name_opt: Option<String>,
@@ -185,7 +219,7 @@ impl Parser for GalleryInfoExtended {
         let pages = parse_pages_4(&root)?;
         let thumb = parse_thumb_4_5(&root)?;
         let rating = parse_rating_4_5(&root)?;
-        let posted = parse_posted(&root)?;
+        let (posted, posted_datetime) = parse_posted(&root)?;
         let is_favorited = parse_is_favorited(&root)?;
         let favorite_slot_opt = parse_favorite_slot_opt(&root)?;
         let favorite_name_opt = parse_favorite_name_opt(&root)?;
@@ -201,6 +235,7 @@ impl Parser for GalleryInfoExtended {
             thumb,
             category,
             posted,
+            posted_datetime,
             uploader,
             rating,
             simple_tag_vec,
@@ -214,16 +249,19 @@ impl Parser for GalleryInfoExtended {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryInfoThumbnail {
     pub gid: u64,
     pub token: String,
     pub title: String,
     pub thumb: Thumb,
     pub category: u32,
+    /// As printed, like `2023-02-07 07:33`.
     pub posted: String,
+    pub posted_datetime: DateTime<Utc>,
     pub rating: f32,
     pub pages: u32,
-    pub simple_language_opt: Option<String>,
+    pub simple_language_opt: Option<Language>,
     pub is_favorited: bool,
     pub favorite_slot_opt: Option<u32>,
     pub favorite_name_opt: Option<String>,
@@ -237,7 +275,7 @@ impl Parser for GalleryInfoThumbnail {
         let pages = parse_pages_5(&root)?;
         let thumb = parse_thumb_4_5(&root)?;
         let rating = parse_rating_4_5(&root)?;
-        let posted = parse_posted(&root)?;
+        let (posted, posted_datetime) = parse_posted(&root)?;
         let is_favorited = parse_is_favorited(&root)?;
         let favorite_slot_opt = parse_favorite_slot_opt(&root)?;
         let favorite_name_opt = parse_favorite_name_opt(&root)?;
@@ -251,6 +289,7 @@ impl Parser for GalleryInfoThumbnail {
             thumb,
             category,
             posted,
+            posted_datetime,
             rating,
             pages,
             simple_language_opt,
@@ -263,15 +302,17 @@ impl Parser for GalleryInfoThumbnail {
 
 fn parse_gid_and_token_1_2_3_5(root: &Elements) -> EhResult<(u64, String)> {
     let a = root.find(r#".glname a"#);
-    let href = a.attr("href").ok_or(ATTRIBUTE_NOT_FOUND)?;
-    let detail_url = GalleryDetailUrl::parse(&href.to_string())?;
+    let context = || context("gid", ".glname a", root);
+    let href = a.attr("href").ok_or(ATTRIBUTE_NOT_FOUND).with_context(context)?;
+    let detail_url = GalleryDetailUrl::parse(&href.to_string()).with_context(context)?;
     Ok((detail_url.gid, detail_url.token))
 }
 
 fn parse_gid_and_token_4(root: &Elements) -> EhResult<(u64, String)> {
     let gl_name = root.find(r#".glname"#);
     let a = gl_name.parent("a");
-    let detail_url = GalleryDetailUrl::parse(&a.text())?;
+    let detail_url = GalleryDetailUrl::parse(&a.text())
+        .with_context(|| context("gid", ".glname", root))?;
     Ok((detail_url.gid, detail_url.token))
 }
 
@@ -279,7 +320,9 @@ fn parse_simple_tag_vec_3_4(root: &Elements) -> EhResult<Vec<String>> {
     let gts = root.find(r#".glname .gt"#);
     let mut simple_tag_vec = Vec::new();
     for gt in gts {
-        let title_attr = gt.get_attribute("title").ok_or(ATTRIBUTE_NOT_FOUND)?;
+        let title_attr = gt.get_attribute("title")
+            .ok_or(ATTRIBUTE_NOT_FOUND)
+            .with_context(|| context("simple_tag_vec", ".glname .gt", root))?;
         simple_tag_vec.push(title_attr.to_string());
     }
 
@@ -307,7 +350,9 @@ fn parse_pages_1_2_3(root: &Elements) -> EhResult<u32> {
     let sibling_str = sibling.text();
 
     let regex = Regex::new(PATTERN_PAGES).unwrap();
-    let captures = regex.captures(&sibling_str).ok_or(REGEX_MATCH_FAILED)?;
+    let captures = regex.captures(&sibling_str)
+        .ok_or(REGEX_MATCH_FAILED)
+        .with_context(|| context("pages", PATTERN_PAGES, &ir.parent("")))?;
 
     Ok(captures[1].parse()?)
 }
@@ -317,7 +362,9 @@ fn parse_pages_4(root: &Elements) -> EhResult<u32> {
     let sibling = ir.next("").next("").text();
 
     let regex = Regex::new(PATTERN_PAGES).unwrap();
-    let captures = regex.captures(&sibling).ok_or(REGEX_MATCH_FAILED)?;
+    let captures = regex.captures(&sibling)
+        .ok_or(REGEX_MATCH_FAILED)
+        .with_context(|| context("pages", PATTERN_PAGES, &ir.parent("")))?;
 
     Ok(captures[1].parse()?)
 }
@@ -327,40 +374,45 @@ fn parse_pages_5(root: &Elements) -> EhResult<u32> {
     let sibling = ir.next("").text();
 
     let regex = Regex::new(PATTERN_PAGES).unwrap();
-    let captures = regex.captures(&sibling).ok_or(REGEX_MATCH_FAILED)?;
+    let captures = regex.captures(&sibling)
+        .ok_or(REGEX_MATCH_FAILED)
+        .with_context(|| context("pages", PATTERN_PAGES, &ir.parent("")))?;
 
     Ok(captures[1].parse()?)
 }
 
 fn parse_thumb_1_2_3(root: &Elements) -> EhResult<Thumb> {
     let img = root.find(r#".glthumb img"#);
-    Ok(Thumb::parse(&img.outer_html())?)
+    Thumb::parse(&img.outer_html()).with_context(|| context("thumb", ".glthumb img", root))
 }
 
 fn parse_thumb_4_5(root: &Elements) -> EhResult<Thumb> {
     let img = root.find("img");
-    Ok(Thumb::parse(&img.outer_html())?)
+    Thumb::parse(&img.outer_html()).with_context(|| context("thumb", "img", root))
 }
 
 fn parse_rating_1_2_3(root: &Elements) -> EhResult<f32> {
     let ir = root.find(r#".glthumb .ir"#);
-    let style = ir.attr("style").ok_or(ATTRIBUTE_NOT_FOUND)?;
-    let rating = Rating::parse(&style.to_string())?;
+    let context = || context("rating", ".glthumb .ir", root);
+    let style = ir.attr("style").ok_or(ATTRIBUTE_NOT_FOUND).with_context(context)?;
+    let rating = Rating::parse(&style.to_string()).with_context(context)?;
 
     Ok(rating.value)
 }
 
 fn parse_rating_4_5(root: &Elements) -> EhResult<f32> {
     let ir = root.find(".ir");
-    let style = ir.attr("style").ok_or(ATTRIBUTE_NOT_FOUND)?;
-    let rating = Rating::parse(&style.to_string())?;
+    let context = || context("rating", ".ir", root);
+    let style = ir.attr("style").ok_or(ATTRIBUTE_NOT_FOUND).with_context(context)?;
+    let rating = Rating::parse(&style.to_string()).with_context(context)?;
 
     Ok(rating.value)
 }
 
-fn parse_posted(root: &Elements) -> EhResult<String> {
-    let posted = root.find("[id^=posted_]");
-    Ok(posted.text())
+fn parse_posted(root: &Elements) -> EhResult<(String, DateTime<Utc>)> {
+    let posted = root.find("[id^=posted_]").text();
+    let posted_datetime = parse_datetime(&posted).with_context(|| context("posted_datetime", "[id^=posted_]", root))?;
+    Ok((posted, posted_datetime))
 }
 
 fn parse_is_favorited(root: &Elements) -> EhResult<bool> {
@@ -400,52 +452,49 @@ fn parse_title(root: &Elements) -> EhResult<String> {
     Ok(link.text())
 }
 
-fn parse_simple_language_opt_1_2_5(root: &Elements) -> EhResult<Option<String>> {
+fn parse_simple_language_opt_1_2_5(root: &Elements) -> EhResult<Option<Language>> {
     let link = root.find(r#".glink"#);
     let idx_opt = S_LANG_PATTERNS.iter().position(|pattern| {
         let regex = Regex::new(pattern).unwrap();
         regex.is_match(&link.text())
     });
 
-    let mut simple_language_opt = None;
-    if let Some(idx) = idx_opt {
-        simple_language_opt = Some(String::from(S_LANGS[idx]));
-    }
-
-    Ok(simple_language_opt)
+    Ok(idx_opt.map(|idx| S_LANGS[idx]))
 }
 
-fn parse_simple_language_opt_3_4(root: &Elements) -> EhResult<Option<String>> {
-    let mut simple_language_opt = None;
+fn parse_simple_language_opt_3_4(root: &Elements) -> EhResult<Option<Language>> {
     let simple_tag_vec = parse_simple_tag_vec_3_4(root)?;
+    let simple_language_opt = simple_tag_vec.iter()
+        .filter(|tag| tag.starts_with("language:"))
+        .find_map(|tag| Language::from_tag(tag));
 
-    for tag in simple_tag_vec {
-        let idx_opt = S_LANG_TAGS.iter().position(|&t| t == tag);
-        if let Some(idx) = idx_opt {
-            simple_language_opt = Some(String::from(S_LANGS[idx]));
-            break;
-        }
-    }
     Ok(simple_language_opt)
 }
 
+/// The helpers are shared by the five modes, [`crate::structures::gallery_list::GalleryList`] tells which one failed.
+fn context(field: &str, locator: &str, root: &Elements) -> ErrorContext {
+    ErrorContext::new("GalleryInfo", field)
+        .locator(locator)
+        .snippet(&root.outer_html())
+}
+
 const PATTERN_PAGES: &str = r#"(\d+) page"#;
 
-const S_LANGS: [&str; 14] = [
-    "S_LANG_EN",
-    "S_LANG_ZH",
-    "S_LANG_ES",
-    "S_LANG_KO",
-    "S_LANG_RU",
-    "S_LANG_FR",
-    "S_LANG_PT",
-    "S_LANG_TH",
-    "S_LANG_DE",
-    "S_LANG_IT",
-    "S_LANG_VI",
-    "S_LANG_PL",
-    "S_LANG_HU",
-    "S_LANG_NL",
+const S_LANGS: [Language; 14] = [
+    Language::English,
+    Language::Chinese,
+    Language::Spanish,
+    Language::Korean,
+    Language::Russian,
+    Language::French,
+    Language::Portuguese,
+    Language::Thai,
+    Language::German,
+    Language::Italian,
+    Language::Vietnamese,
+    Language::Polish,
+    Language::Hungarian,
+    Language::Dutch,
 ];
 
 const S_LANG_PATTERNS: [&str; 14] = [
@@ -466,23 +515,6 @@ const S_LANG_PATTERNS: [&str; 14] = [
     r#"[(\[]dutch[)\]]|オランダ翻訳"#,
 ];
 
-const S_LANG_TAGS: [&str; 14] = [
-    "language:english",
-    "language:chinese",
-    "language:spanish",
-    "language:korean",
-    "language:russian",
-    "language:french",
-    "language:portuguese",
-    "language:thai",
-    "language:german",
-    "language:italian",
-    "language:vietnamese",
-    "language:polish",
-    "language:hungarian",
-    "language:dutch",
-];
-
 // const PATTERN_THUMB_SIZE: &str = r#"height:(\d+)px;width:(\d+)px"#;
 
 // const S_LANG_JA: &str = "JA";
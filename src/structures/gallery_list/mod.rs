@@ -8,10 +8,12 @@ pub use gallery_info::{
     GalleryInfoThumbnail,
 };
 
-use visdom::Vis;
-use crate::{EhResult, Parser};
+use visdom::{types::Elements, Vis};
+use crate::{Context, EhResult, ErrorContext, ParseError, Parser, page_kind::pre_check, structures::LayoutMode};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "mode", content = "gallery_info_vec"))]
 pub enum GalleryList {
     Minimal(Vec<GalleryInfoMinimal>),
     MinimalPlus(Vec<GalleryInfoMinimalPlus>),
@@ -22,54 +24,112 @@ pub enum GalleryList {
 
 
 impl Parser for GalleryList {
+    /// The mode comes from the `inline_set` dropdown, by value then by text,
+    /// or from the class of `.itg` when the dropdown is missing.
     fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
+
         let root = Vis::load(doc)?;
 
         let selector = r#".searchnav select[onchange*=inline_set] > option[selected]"#;
-        let selected = root.find(selector).last();
+        let selected = root.find(selector);
+        // `last` panics on an empty set.
+        let selected = if selected.is_empty() { selected } else { selected.last() };
 
         let itg = root.find(".itg");
-        match selected.text().as_str() {
-            "Minimal" => {
+        let mode = layout_from_dropdown(&selected)
+            .or_else(|| layout_from_itg(&itg))
+            .ok_or_else(|| ParseError::UnrecognizedLayout(format!(
+                "gallery list, dropdown: {:?}, itg: {:?}",
+                selected.text(),
+                itg.attr("class").map(|class| class.to_string()).unwrap_or_default(),
+            )))?;
+
+        match mode {
+            LayoutMode::Minimal => {
                 let mut vec = Vec::new();
-                for child in itg.children("tr").slice(1..) {
-                    vec.push(GalleryInfoMinimal::parse(&child.outer_html())?)
+                for (idx, child) in itg.children("tr").slice(1..).into_iter().enumerate() {
+                    let gallery_info = GalleryInfoMinimal::parse(&child.outer_html())
+                        .with_context(|| ErrorContext::new(PARSER, &format!("Minimal[{}]", idx)))?;
+                    vec.push(gallery_info);
                 }
                 Ok(GalleryList::Minimal(vec))
             }
-            "Minimal+" => {
+            LayoutMode::MinimalPlus => {
                 let mut vec = Vec::new();
-                for child in itg.children("tr").slice(1..) {
-                    vec.push(GalleryInfoMinimalPlus::parse(&child.outer_html())?)
+                for (idx, child) in itg.children("tr").slice(1..).into_iter().enumerate() {
+                    let gallery_info = GalleryInfoMinimalPlus::parse(&child.outer_html())
+                        .with_context(|| ErrorContext::new(PARSER, &format!("MinimalPlus[{}]", idx)))?;
+                    vec.push(gallery_info);
                 }
                 Ok(GalleryList::MinimalPlus(vec))
             }
-            "Compact" => {
+            LayoutMode::Compact => {
                 let mut vec = Vec::new();
-                for child in itg.children("tr").slice(1..) {
-                    vec.push(GalleryInfoCompact::parse(&child.outer_html())?)
+                for (idx, child) in itg.children("tr").slice(1..).into_iter().enumerate() {
+                    let gallery_info = GalleryInfoCompact::parse(&child.outer_html())
+                        .with_context(|| ErrorContext::new(PARSER, &format!("Compact[{}]", idx)))?;
+                    vec.push(gallery_info);
                 }
                 Ok(GalleryList::Compact(vec))
             }
-            "Extended" => {
+            LayoutMode::Extended => {
                 let mut vec = Vec::new();
-                for child in itg.children("tr") {
-                    vec.push(GalleryInfoExtended::parse(&child.outer_html())?)
+                for (idx, child) in itg.children("tr").into_iter().enumerate() {
+                    let gallery_info = GalleryInfoExtended::parse(&child.outer_html())
+                        .with_context(|| ErrorContext::new(PARSER, &format!("Extended[{}]", idx)))?;
+                    vec.push(gallery_info);
                 }
                 Ok(GalleryList::Extended(vec))
             }
-            "Thumbnail" => {
+            LayoutMode::Thumbnail => {
                 let mut vec = Vec::new();
-                for child in itg.children(".gl1t") {
-                    vec.push(GalleryInfoThumbnail::parse(&child.outer_html())?)
+                for (idx, child) in itg.children(".gl1t").into_iter().enumerate() {
+                    let gallery_info = GalleryInfoThumbnail::parse(&child.outer_html())
+                        .with_context(|| ErrorContext::new(PARSER, &format!("Thumbnail[{}]", idx)))?;
+                    vec.push(gallery_info);
                 }
                 Ok(GalleryList::Thumbnail(vec))
             }
-            _ => unreachable!(),
         }
     }
 }
 
+const PARSER: &str = "GalleryList";
+
+/// The value of the option is the one of the `dm` setting, the text may be translated.
+fn layout_from_dropdown(selected: &Elements) -> Option<LayoutMode> {
+    let value = selected.attr("value").map(|value| value.to_string()).unwrap_or_default();
+    LayoutMode::from_value(&value).or(match selected.text().as_str() {
+        "Minimal" => Some(LayoutMode::Minimal),
+        "Minimal+" => Some(LayoutMode::MinimalPlus),
+        "Compact" => Some(LayoutMode::Compact),
+        "Extended" => Some(LayoutMode::Extended),
+        "Thumbnail" => Some(LayoutMode::Thumbnail),
+        _ => None,
+    })
+}
+
+/// ```html
+/// <table class="itg gltm">    Minimal, Minimal+ shares the table and is read as Minimal.
+/// <table class="itg gltc">    Compact
+/// <table class="itg glte">    Extended
+/// <div class="itg gld">       Thumbnail
+/// ```
+fn layout_from_itg(itg: &Elements) -> Option<LayoutMode> {
+    if itg.has_class("gltm") {
+        Some(LayoutMode::Minimal)
+    } else if itg.has_class("gltc") {
+        Some(LayoutMode::Compact)
+    } else if itg.has_class("glte") {
+        Some(LayoutMode::Extended)
+    } else if itg.has_class("gld") {
+        Some(LayoutMode::Thumbnail)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test_helper::read_test_file;
@@ -92,4 +152,20 @@ mod tests {
         let doc = read_test_file("gallery_list_thumbnail.html");
         let result = GalleryList::parse(&doc);
     }
+
+    #[test]
+    fn parse_layout_test() {
+        let doc = r#"<div class="searchnav"><select onchange="inline_set"><option value="m">Minimal</option><option value="t" selected="selected">Vorschaubilder</option></select></div><div class="itg gld"></div>"#;
+        assert_eq!(GalleryList::parse(doc).unwrap(), GalleryList::Thumbnail(Vec::new()));
+
+        let doc = r#"<table class="itg glte"></table>"#;
+        assert_eq!(GalleryList::parse(doc).unwrap(), GalleryList::Extended(Vec::new()));
+
+        let doc = r#"<div class="itg gl9x"></div>"#;
+        assert!(matches!(GalleryList::parse(doc), Err(ParseError::UnrecognizedLayout(_))));
+
+        assert!(matches!(GalleryList::parse(""), Err(ParseError::SignInRequired)));
+        let doc = "Your IP address has been temporarily banned for excessive pageloads. The ban expires in 1 hour";
+        assert!(matches!(GalleryList::parse(doc), Err(ParseError::IpBanned(_))));
+    }
 }
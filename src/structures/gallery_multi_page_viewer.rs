@@ -0,0 +1,159 @@
+use regex::Regex;
+use serde::Deserialize;
+use crate::{Context, EhResult, ErrorContext, Parser, REGEX_MATCH_FAILED, page_kind::pre_check};
+
+/// The multi-page viewer, `https://e-hentai.org/mpv/2455981/acc72caed0/`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryMultiPageViewer {
+    pub gid: u64,
+    /// Sent with every image request of the viewer.
+    pub mpv_key: String,
+    pub page_count: u32,
+    pub image_vec: Vec<GalleryMultiPageViewerImage>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryMultiPageViewerImage {
+    /// Starts from 0.
+    pub page: u32,
+    /// The filename, like `001.jpg`.
+    pub name: String,
+    /// The p-token of [`crate::structures::GalleryPageUrl`].
+    pub p_token: String,
+    /// The sprite shared by up to 20 pages, or the thumbnail of its own.
+    pub thumb_url: String,
+    /// The thumbnail starts `offset_x` pixels from the left of the sprite, 0 without a sprite.
+    pub offset_x: u32,
+    pub offset_y: u32,
+}
+
+impl Parser for GalleryMultiPageViewer {
+    /// ```html
+    /// <script type="text/javascript">
+    /// var gid = 2455981;
+    /// var mpvkey = "0a2b4c6d8e";
+    /// var pagecount = 2;
+    /// var imagelist = [{"n":"001.jpg","k":"35142216f7","t":"(https:\/\/ehgt.org\/m\/002455\/2455981-00.jpg) -0px 0"},
+    ///     {"n":"002.jpg","k":"8a3b1f9e22","t":"(https:\/\/ehgt.org\/m\/002455\/2455981-00.jpg) -100px 0"}];
+    /// </script>
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
+
+        let regex = Regex::new(PATTERN_GID).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "gid").locator(PATTERN_GID))?;
+        let gid = captures[1].parse()
+            .context(ErrorContext::new(PARSER, "gid").locator(PATTERN_GID))?;
+
+        let regex = Regex::new(PATTERN_MPV_KEY).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "mpv_key").locator(PATTERN_MPV_KEY))?;
+        let mpv_key = String::from(&captures[1]);
+
+        let regex = Regex::new(PATTERN_PAGE_COUNT).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "page_count").locator(PATTERN_PAGE_COUNT))?;
+        let page_count = captures[1].parse()
+            .context(ErrorContext::new(PARSER, "page_count").locator(PATTERN_PAGE_COUNT))?;
+
+        let regex = Regex::new(PATTERN_IMAGE_LIST).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "image_vec").locator(PATTERN_IMAGE_LIST))?;
+        let image_list = &captures[1];
+        let internal_vec = serde_json::from_str::<Vec<GalleryMultiPageViewerImageInternal>>(image_list)
+            .with_context(|| ErrorContext::new(PARSER, "image_vec").locator(PATTERN_IMAGE_LIST).snippet(image_list))?;
+
+        let regex = Regex::new(PATTERN_THUMB_URL).unwrap();
+        let mut image_vec = Vec::with_capacity(internal_vec.len());
+        for (page, internal) in internal_vec.into_iter().enumerate() {
+            let field = format!("image_vec[{}]", page);
+            let captures = regex.captures(&internal.t)
+                .ok_or(REGEX_MATCH_FAILED)
+                .with_context(|| ErrorContext::new(PARSER, &field).locator(PATTERN_THUMB_URL).snippet(&internal.t))?;
+            let thumb_url = String::from(&captures[1]);
+            let offset = |idx: usize| -> EhResult<u32> {
+                match captures.get(idx) {
+                    Some(offset) => Ok(offset.as_str().parse::<i64>()?.unsigned_abs() as u32),
+                    None => Ok(0),
+                }
+            };
+            let offset_x = offset(2)
+                .with_context(|| ErrorContext::new(PARSER, &field).locator(PATTERN_THUMB_URL).snippet(&internal.t))?;
+            let offset_y = offset(3)
+                .with_context(|| ErrorContext::new(PARSER, &field).locator(PATTERN_THUMB_URL).snippet(&internal.t))?;
+
+            image_vec.push(GalleryMultiPageViewerImage {
+                page: page as u32,
+                name: internal.n,
+                p_token: internal.k,
+                thumb_url,
+                offset_x,
+                offset_y,
+            });
+        }
+
+        Ok(GalleryMultiPageViewer {
+            gid,
+            mpv_key,
+            page_count,
+            image_vec,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GalleryMultiPageViewerImageInternal {
+    n: String,
+    k: String,
+    t: String,
+}
+
+const PARSER: &str = "GalleryMultiPageViewer";
+const PATTERN_GID: &str = r#"var gid\s*=\s*(\d+);"#;
+const PATTERN_MPV_KEY: &str = r#"var mpvkey\s*=\s*"([0-9a-z]+)";"#;
+const PATTERN_PAGE_COUNT: &str = r#"var pagecount\s*=\s*(\d+);"#;
+const PATTERN_IMAGE_LIST: &str = r#"(?s)var imagelist\s*=\s*(\[.*?\]);"#;
+const PATTERN_THUMB_URL: &str = r#"(https?://[^)\s"]+)\)?(?:\s*(-?\d+)(?:px)?\s+(-?\d+)(?:px)?)?"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let doc = r#"
+            <script type="text/javascript">
+            var gid = 2455981;
+            var mpvkey = "0a2b4c6d8e";
+            var pagecount = 2;
+            var imagelist = [{"n":"001.jpg","k":"35142216f7","t":"(https:\/\/ehgt.org\/m\/002455\/2455981-00.jpg) -0px 0"},
+                {"n":"002;].jpg","k":"8a3b1f9e22","t":"url(https:\/\/ehgt.org\/m\/002455\/2455981-00.jpg) -100px 0"}];
+            </script>
+        "#;
+        let mpv = GalleryMultiPageViewer::parse(doc).unwrap();
+        assert_eq!(mpv.gid, 2455981);
+        assert_eq!(mpv.mpv_key, "0a2b4c6d8e");
+        assert_eq!(mpv.page_count, 2);
+        assert_eq!(mpv.image_vec[1], GalleryMultiPageViewerImage {
+            page: 1,
+            name: String::from("002;].jpg"),
+            p_token: String::from("8a3b1f9e22"),
+            thumb_url: String::from("https://ehgt.org/m/002455/2455981-00.jpg"),
+            offset_x: 100,
+            offset_y: 0,
+        });
+        assert_eq!((mpv.image_vec[0].offset_x, mpv.image_vec[0].offset_y), (0, 0));
+
+        let error = GalleryMultiPageViewer::parse(&doc.replace("var imagelist", "var list")).unwrap_err();
+        assert_eq!(error.context_chain()[0].field, "image_vec");
+        assert!(GalleryMultiPageViewer::parse(&doc.replace(r#""k":"35142216f7","#, "")).is_err());
+        assert!(GalleryMultiPageViewer::parse("").is_err());
+    }
+}
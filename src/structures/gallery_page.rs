@@ -1,51 +1,123 @@
 use regex::Regex;
-use crate::{EhResult, Parser, REGEX_MATCH_FAILED, unescape::unescape};
+use crate::{Context, EhResult, ErrorContext, Parser, REGEX_MATCH_FAILED, page_kind::pre_check, structures::GalleryImage};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryPage {
-    pub image_url: String,
-    pub skip_hath_key: String,
-    pub origin_image_url: String,
+    pub image: GalleryImage,
+    /// For [`crate::structures::GalleryPageApi`] on the next pages.
     pub show_key: String,
 }
 
 impl Parser for GalleryPage {
+    /// ```html
+    /// <div id="i1" class="sni" style="width:1280px">
+    ///     <h1>[Pixiv] Title</h1>
+    ///     <div id="i2"><div class="sn">...</div><div>002.jpg :: 1280 x 1810 :: 354.6 KiB</div></div>
+    ///     <div id="i3"><a onclick="return load_image(3, 'c3a1b0d9e8')" href="https://e-hentai.org/s/c3a1b0d9e8/2455981-3"><img id="img" src="..." style="..." onerror="..."></a></div>
+    ///     <div id="i6" class="if">... <a href="#" id="loadfail" onclick="return nl('42377-430136')">Reload broken image</a></div>
+    ///     <div id="i7" class="if"><a href="https://e-hentai.org/fullimg/2455981/2/3nk7sa2fyfj/002.jpg">Download original 2400 x 3394 1.75 MiB source</a></div>
+    /// </div>
+    /// <script type="text/javascript">
+    /// var gid=2455981;
+    /// var startpage=2;
+    /// var startkey="8a3b1f9e22";
+    /// var showkey="6wq1xr7c1mo";
+    /// </script>
+    /// ```
+    /// See [`GalleryImage`] for the navigation.
+    /// The quota, ban, removed and key pages are the errors of [`crate::ParseError`] of the same names.
     fn parse(doc: &str) -> EhResult<Self> {
-        let regex = Regex::new(PATTERN_IMAGE_URL).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
-        let image_url = String::from(&captures[1]);
+        pre_check(doc)?;
 
-        let regex = Regex::new(PATTERN_SKIP_HATH_KEY).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
-        let skip_hath_key = String::from(&captures[1]);
+        let regex = Regex::new(PATTERN_START).unwrap();
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "image").locator(PATTERN_START))?;
+        let page = captures[1].parse::<u32>()
+            .context(ErrorContext::new(PARSER, "image").locator(PATTERN_START))?;
+        let p_token = String::from(&captures[2]);
 
-        let regex = Regex::new(PATTERN_ORIGIN_IMAGE_URL).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
-        let origin_image_url = format!("{}{}{}", &captures[1], r#"fullimg.php"#, unescape(&captures[2]));
+        // startpage starts from 1.
+        // the fields carry the contexts of GalleryImage, the 509 image comes out as is.
+        let image = GalleryImage::parse_page(doc, page.saturating_sub(1), p_token)?;
 
         let regex = Regex::new(PATTERN_SHOW_KEY).unwrap();
-        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
+        let captures = regex.captures(doc)
+            .ok_or(REGEX_MATCH_FAILED)
+            .context(ErrorContext::new(PARSER, "show_key").locator(PATTERN_SHOW_KEY))?;
         let show_key = String::from(&captures[1]);
 
         Ok(GalleryPage {
-            image_url,
-            skip_hath_key,
-            origin_image_url,
+            image,
             show_key,
         })
     }
 }
 
-const PATTERN_IMAGE_URL: &str = r#"<img[^>]*src="([^"]+)" style"#;
-const PATTERN_SKIP_HATH_KEY: &str = r#"onclick="return nl\('([^\)]+)'\)"#;
-const PATTERN_ORIGIN_IMAGE_URL: &str = r#"<a href=\"([^\"]+)fullimg.php([^\"]+)\">"#;
+const PARSER: &str = "GalleryPage";
+const PATTERN_START: &str = r#"var startpage\s*=\s*(\d+);\s*var startkey\s*=\s*"([0-9a-f]+)";"#;
 // TODO Not sure about the size of show keys
-const PATTERN_SHOW_KEY: &str = r#"var showkey="([0-9a-z]+)";"#;
+const PATTERN_SHOW_KEY: &str = r#"var showkey\s*=\s*"([0-9a-z]+)";"#;
 
 #[cfg(test)]
 mod tests {
+    use crate::structures::GalleryPageUrl;
     use super::*;
 
     #[test]
-    fn parse_test() {}
+    fn parse_test() {
+        let doc = r##"
+            <div id="i1" class="sni" style="width:1280px">
+                <h1>[Pixiv] Title</h1>
+                <div id="i2">
+                    <div class="sn"><a onclick="return load_image(1, '35142216f7')" href="https://e-hentai.org/s/35142216f7/2455981-1"><img src="https://ehgt.org/g/f.png"></a><a
+                            id="prev" onclick="return load_image(1, '35142216f7')" href="https://e-hentai.org/s/35142216f7/2455981-1"><img
+                                src="https://ehgt.org/g/p.png"></a>
+                        <div><span>2</span> / <span>3</span></div><a id="next" onclick="return load_image(3, 'c3a1b0d9e8')"
+                            href="https://e-hentai.org/s/c3a1b0d9e8/2455981-3"><img src="https://ehgt.org/g/n.png"></a><a
+                            onclick="return load_image(3, 'c3a1b0d9e8')" href="https://e-hentai.org/s/c3a1b0d9e8/2455981-3"><img src="https://ehgt.org/g/l.png"></a></div>
+                    <div>002 &amp; more.jpg :: 1280 x 1810 :: 354.6 KiB</div>
+                </div>
+                <div id="i3"><a onclick="return load_image(3, 'c3a1b0d9e8')" href="https://e-hentai.org/s/c3a1b0d9e8/2455981-3"><img id="img"
+                            src="https://abcdefg.hath.network/h/abc-362922-1280-1810-jpg/keystamp=1690000000-5e8f1c2b3a;fileindex=123456789;xres=1280/002.jpg"
+                            style="height:1810px;width:1280px;max-width:1280px;max-height:1810px" onerror="this.onerror=null; nl('42377-430136')"></a></div>
+                <div id="i6" class="if"><a href="#" id="loadfail" onclick="return nl('42377-430136')">Reload broken image</a></div>
+                <div id="i7" class="if"><img class="mr" src="https://ehgt.org/g/mr.gif"> <a
+                        href="https://e-hentai.org/fullimg/2455981/2/3nk7sa2fyfj/002.jpg">Download original 2400 x 3394 1.75 MiB source</a></div>
+            </div>
+            <script type="text/javascript">
+            var gid=2455981;
+            var startpage=2;
+            var startkey="8a3b1f9e22";
+            var showkey="6wq1xr7c1mo";
+            </script>
+        "##;
+        let gallery_page = GalleryPage::parse(doc).unwrap();
+        assert_eq!(gallery_page.show_key, "6wq1xr7c1mo");
+
+        let image = gallery_page.image;
+        assert_eq!(image.page_url, GalleryPageUrl { gid: 2455981, p_token: String::from("8a3b1f9e22"), page: 1 });
+        assert_eq!(image.page_count, 3);
+        assert_eq!(image.filename, "002 & more.jpg");
+        assert_eq!((image.width, image.height, image.file_size), (1280, 1810, 363110));
+        assert_eq!(image.skip_hath_key, "42377-430136");
+        assert_eq!(image.origin_image_url_opt.as_deref(), Some("https://e-hentai.org/fullimg/2455981/2/3nk7sa2fyfj/002.jpg"));
+        assert_eq!(image.prev_page_url_opt, Some(GalleryPageUrl { gid: 2455981, p_token: String::from("35142216f7"), page: 0 }));
+        assert_eq!(image.next_page_url_opt, Some(GalleryPageUrl { gid: 2455981, p_token: String::from("c3a1b0d9e8"), page: 2 }));
+
+        let doc = doc.replace("var startpage=2", "var startpage=1").replace("href=\"https://e-hentai.org/s/35142216f7/2455981-1\"", "href=\"https://e-hentai.org/s/8a3b1f9e22/2455981-1\"");
+        let image = GalleryPage::parse(&doc).unwrap().image;
+        assert_eq!(image.prev_page_url_opt, None);
+
+        let error = GalleryPage::parse(&doc.replace(":: 1280 x 1810 ::", "::")).unwrap_err();
+        assert_eq!(error.context_chain()[0].field, "filename");
+
+        let error = GalleryPage::parse(&doc.replace("https://abcdefg.hath.network/h/abc-362922-1280-1810-jpg/keystamp=1690000000-5e8f1c2b3a;fileindex=123456789;xres=1280/002.jpg", "https://ehgt.org/g/509.gif")).unwrap_err();
+        assert!(matches!(error, crate::ParseError::ImageLimitExceeded));
+        let doc = "<html><body>Your IP address has been temporarily banned for excessive pageloads which indicates that you are using automated mirroring/harvesting software. The ban expires in 59 minutes and 30 seconds</body></html>";
+        assert!(matches!(GalleryPage::parse(doc), Err(crate::ParseError::IpBanned(duration)) if duration.as_secs() == 3570));
+        let doc = "<html><body>Key missing, or incorrect key provided.</body></html>";
+        assert!(matches!(GalleryPage::parse(doc), Err(crate::ParseError::KeyExpired)));
+    }
 }
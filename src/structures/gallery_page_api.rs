@@ -1,44 +1,49 @@
-use regex::Regex;
 use serde::{Deserialize, Serialize};
-use crate::{EhResult, Parser, REGEX_MATCH_FAILED, unescape::unescape};
+use crate::{EhResult, Parser, page_kind::pre_check, structures::GalleryImage};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryPageApi {
-    pub image_url: String,
-    pub skip_hath_key: String,
-    pub origin_image_url: String,
+    pub image: GalleryImage,
 }
 
 impl Parser for GalleryPageApi {
+    /// ```json
+    /// {
+    ///     "p": 3,
+    ///     "k": "c3a1b0d9e8",
+    ///     "n": "<div class=\"sn\">...<\/div>",
+    ///     "i": "<div>003.jpg :: 1280 x 1810 :: 354.6 KiB<\/div>",
+    ///     "i3": "<a onclick=\"return load_image(4, '...')\" href=\"...\"><img id=\"img\" src=\"...\" style=\"...\" onerror=\"...\"><\/a>",
+    ///     "i6": "... <a href=\"#\" id=\"loadfail\" onclick=\"return nl('42377-430136')\">Reload broken image<\/a>",
+    ///     "i7": "<a href=\"https:\/\/e-hentai.org\/fullimg\/...\">Download original ...<\/a>"
+    /// }
+    /// ```
+    /// Or
+    /// ```json
+    /// {"error": "Key mismatch"}
+    /// ```
+    /// The html parts are read like [`crate::structures::GalleryPage`].
     fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
         let internal = serde_json::from_str::<GalleryPageApiInternal>(doc)?;
 
-        let regex = Regex::new(PATTERN_IMAGE_URL).unwrap();
-        let captures = regex.captures(&internal.i3).ok_or(REGEX_MATCH_FAILED)?;
-        let image_url = String::from(&captures[1]);
-
-        let regex = Regex::new(PATTERN_SKIP_HATH_KEY).unwrap();
-        let captures = regex.captures(&internal.i6).ok_or(REGEX_MATCH_FAILED)?;
-        let skip_hath_key = String::from(&captures[1]);
-
-        let regex = Regex::new(PATTERN_ORIGIN_IMAGE_URL).unwrap();
-        let captures = regex.captures(&internal.i7).ok_or(REGEX_MATCH_FAILED)?;
-        let origin_image_url = format!("{}{}{}", &captures[1], r#"fullimg.php"#, unescape(&captures[2]));
+        // p starts from 1.
+        let html = format!("{}{}{}{}{}", internal.n, internal.i, internal.i3, internal.i6, internal.i7);
+        let image = GalleryImage::parse_page(&html, internal.p.saturating_sub(1), internal.k)?;
 
         Ok(GalleryPageApi {
-            image_url,
-            skip_hath_key,
-            origin_image_url,
+            image,
         })
     }
 }
 
-const PATTERN_IMAGE_URL: &str = r#"<img[^>]*src="([^"]+)" style"#;
-const PATTERN_SKIP_HATH_KEY: &str = r#"onclick="return nl\('([^\)]+)'\)"#;
-const PATTERN_ORIGIN_IMAGE_URL: &str = r#"<a href="([^"]+)fullimg.php([^"]+)">"#;
-
 #[derive(Debug, Serialize, Deserialize)]
 struct GalleryPageApiInternal {
+    p: u32,
+    k: String,
+    n: String,
+    i: String,
     i3: String,
     i6: String,
     i7: String,
@@ -50,5 +55,20 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parse_test() {}
+    fn parse_test() {
+        let doc = r##"{"p":3,"s":"s\/c3a1b0d9e8\/2455981-3","n":"<div class=\"sn\"><a onclick=\"return load_image(1, '35142216f7')\" href=\"https:\/\/e-hentai.org\/s\/35142216f7\/2455981-1\"><img src=\"https:\/\/ehgt.org\/g\/f.png\"><\/a><a id=\"prev\" onclick=\"return load_image(2, '8a3b1f9e22')\" href=\"https:\/\/e-hentai.org\/s\/8a3b1f9e22\/2455981-2\"><img src=\"https:\/\/ehgt.org\/g\/p.png\"><\/a><div><span>3<\/span> \/ <span>3<\/span><\/div><a id=\"next\" onclick=\"return load_image(3, 'c3a1b0d9e8')\" href=\"https:\/\/e-hentai.org\/s\/c3a1b0d9e8\/2455981-3\"><img src=\"https:\/\/ehgt.org\/g\/n.png\"><\/a><\/div>","i":"<div>003.jpg :: 1280 x 1810 :: 1.2 MiB<\/div>","k":"c3a1b0d9e8","i3":"<a onclick=\"return load_image(3, 'c3a1b0d9e8')\" href=\"https:\/\/e-hentai.org\/s\/c3a1b0d9e8\/2455981-3\"><img id=\"img\" src=\"https:\/\/abcdefg.hath.network\/h\/003.jpg\" style=\"height:1810px;width:1280px\" onerror=\"this.onerror=null; nl('42377-430137')\"><\/a>","i5":"","i6":" &nbsp; <a href=\"#\" id=\"loadfail\" onclick=\"return nl('42377-430137')\">Reload broken image<\/a>","i7":"","si":430137,"x":"1280","y":"1810"}"##;
+        let image = GalleryPageApi::parse(doc).unwrap().image;
+        assert_eq!(image.page_url.page, 2);
+        assert_eq!(image.page_url.gid, 2455981);
+        assert_eq!(image.file_size, 1258291);
+        assert_eq!(image.origin_image_url_opt, None);
+        assert_eq!(image.prev_page_url_opt.map(|page_url| page_url.page), Some(1));
+        assert_eq!(image.next_page_url_opt, None);
+
+        assert!(matches!(GalleryPageApi::parse(r#"{"error":"Key mismatch"}"#), Err(crate::ParseError::KeyExpired)));
+        let doc = r#"{"error":"You have exceeded your image viewing limits"}"#;
+        assert!(matches!(GalleryPageApi::parse(doc), Err(crate::ParseError::ImageLimitExceeded)));
+        let doc = r#"{"error":"Your IP address has been temporarily banned for excessive pageloads. The ban expires in 1 hour"}"#;
+        assert!(matches!(GalleryPageApi::parse(doc), Err(crate::ParseError::IpBanned(duration)) if duration.as_secs() == 3600));
+    }
 }
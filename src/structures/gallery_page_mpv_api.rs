@@ -0,0 +1,121 @@
+use serde::Deserialize;
+use crate::{Context, EhResult, ErrorContext, Parser, Site, file_size::parse_file_size, page_kind::pre_check};
+
+/// The answer of the `imagedispatch` method, what the multi-page viewer shows for a page.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryPageMpvApi {
+    pub image_url: String,
+    pub width: u32,
+    pub height: u32,
+    /// In bytes, of the image shown, not of the original.
+    pub file_size: u64,
+    /// Like `fullimg/2455981/1/3nk7sa2fyfj/001.jpg`, see [`GalleryPageMpvApi::origin_image_url`].
+    pub origin_image_path: String,
+    /// The server to skip when the image fails to load, `s` in the answer.
+    pub skip_hath_key: String,
+}
+
+impl GalleryPageMpvApi {
+    pub fn origin_image_url(&self, site: Site) -> String {
+        format!("{}{}", site.host(), self.origin_image_path)
+    }
+}
+
+impl Parser for GalleryPageMpvApi {
+    /// ```json
+    /// {
+    ///     "d": "1280 x 1810 :: 354.6 KiB",
+    ///     "o": "Download original 2400 x 3394 1.75 MiB source",
+    ///     "lf": "fullimg\/2455981\/1\/3nk7sa2fyfj\/001.jpg",
+    ///     "ls": "?f_shash=7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c&fs_from=001.jpg+from+xxxx",
+    ///     "ll": "2455981-1\/7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c",
+    ///     "lo": "s\/35142216f7\/2455981-1",
+    ///     "xres": "1280",
+    ///     "yres": "1810",
+    ///     "i": "https:\/\/abcdefg.hijklmnopqrs.hath.network\/h\/7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c-362922-1280-1810-jpg\/keystamp=1690000000-5e8f1c2b3a;fileindex=123456789;xres=1280\/001.jpg",
+    ///     "s": "42377"
+    /// }
+    /// ```
+    /// Or
+    /// ```json
+    /// {"error": "Key mismatch"}
+    /// ```
+    /// See [`GalleryImage::from_mpv`](crate::structures::GalleryImage::from_mpv) for the 509 image.
+    fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
+        let internal = serde_json::from_str::<GalleryPageMpvApiInternal>(doc)?;
+        let context = |field: &str, locator: &str, snippet: &str| ErrorContext::new(PARSER, field).locator(locator).snippet(snippet);
+
+        let width = internal.xres.value()
+            .with_context(|| context("width", "xres", &internal.xres.text()))?;
+        let height = internal.yres.value()
+            .with_context(|| context("height", "yres", &internal.yres.text()))?;
+        let file_size = parse_file_size(&internal.d)
+            .with_context(|| context("file_size", "d", &internal.d))?;
+
+        Ok(GalleryPageMpvApi {
+            image_url: internal.i,
+            width,
+            height,
+            file_size,
+            origin_image_path: internal.lf,
+            skip_hath_key: internal.s.text(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GalleryPageMpvApiInternal {
+    d: String,
+    lf: String,
+    xres: NumberInternal,
+    yres: NumberInternal,
+    i: String,
+    s: NumberInternal,
+}
+
+/// The numbers come as strings or as numbers.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NumberInternal {
+    Number(u64),
+    String(String),
+}
+
+impl NumberInternal {
+    fn text(&self) -> String {
+        match self {
+            NumberInternal::Number(number) => number.to_string(),
+            NumberInternal::String(string) => string.trim().to_string(),
+        }
+    }
+
+    fn value(&self) -> EhResult<u32> {
+        Ok(self.text().parse()?)
+    }
+}
+
+const PARSER: &str = "GalleryPageMpvApi";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let doc = r#"{"d":"1280 x 1810 :: 354.6 KiB","o":"Download original 2400 x 3394 1.75 MiB source","lf":"fullimg\/2455981\/1\/3nk7sa2fyfj\/001.jpg","ls":"?f_shash=7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c&fs_from=001.jpg+from+xxxx","ll":"2455981-1\/7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c","lo":"s\/35142216f7\/2455981-1","xres":"1280","yres":1810,"i":"https:\/\/abcdefg.hijklmnopqrs.hath.network\/h\/7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c-362922-1280-1810-jpg\/keystamp=1690000000-5e8f1c2b3a;fileindex=123456789;xres=1280\/001.jpg","s":"42377"}"#;
+        let api = GalleryPageMpvApi::parse(doc).unwrap();
+        assert_eq!(api.image_url, "https://abcdefg.hijklmnopqrs.hath.network/h/7a3c95b1ed2e3ae9c1a8e1de1236e26a9c6bcb5c-362922-1280-1810-jpg/keystamp=1690000000-5e8f1c2b3a;fileindex=123456789;xres=1280/001.jpg");
+        assert_eq!((api.width, api.height), (1280, 1810));
+        assert_eq!(api.file_size, 363110);
+        assert_eq!(api.origin_image_url(Site::Ex), "https://exhentai.org/fullimg/2455981/1/3nk7sa2fyfj/001.jpg");
+        assert_eq!(api.skip_hath_key, "42377");
+
+        let error = GalleryPageMpvApi::parse(&doc.replace(r#""xres":"1280""#, r#""xres":"wide""#)).unwrap_err();
+        assert_eq!(error.context_chain()[0].field, "width");
+        let error = GalleryPageMpvApi::parse(&doc.replace("354.6 KiB", "big")).unwrap_err();
+        assert_eq!(error.context_chain()[0].field, "file_size");
+        assert!(matches!(GalleryPageMpvApi::parse(r#"{"error":"Key mismatch"}"#), Err(crate::ParseError::KeyExpired)));
+    }
+}
@@ -2,6 +2,7 @@ use regex::Regex;
 use crate::{const_concat, eh_url, EhResult, Parser, REGEX_MATCH_FAILED};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryPageUrl {
     pub gid: u64,
     pub p_token: String,
@@ -1,10 +1,22 @@
 use visdom::Vis;
-use crate::{EhResult, Parser};
+use crate::{EhResult, Parser, structures::{Namespace, Tag}};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryTagGroup {
     pub tag_group_name: String,
-    pub tag_vec: Vec<String>,
+    pub tag_vec: Vec<Tag>,
+}
+
+impl GalleryTagGroup {
+    /// `None` for a namespace newer than [`Namespace`].
+    pub fn namespace_opt(&self) -> Option<Namespace> {
+        // the older pages name the row of the tags without namespace `misc`.
+        if self.tag_group_name == "misc" {
+            return Some(Namespace::Other);
+        }
+        self.tag_group_name.parse().ok()
+    }
 }
 
 impl ToString for GalleryTagGroup {
@@ -28,15 +40,28 @@ impl Parser for GalleryTagGroup {
     fn parse(doc: &str) -> EhResult<Self> {
         let root = Vis::load(doc)?;
         let tag_group_name = root.find(".tc").text();
-        let tag_group_name = String::from(&tag_group_name[..tag_group_name.len() - 1]);
-
-        // gt or gtl.
-        let tag_vec = root.find("[class^=gt]").map(|_, ele| ele.text());
+        let tag_group_name = String::from(tag_group_name.strip_suffix(':').unwrap_or(&tag_group_name));
 
-        Ok(GalleryTagGroup {
+        let mut tag_group = GalleryTagGroup {
             tag_group_name,
-            tag_vec,
-        })
+            tag_vec: Vec::new(),
+        };
+        // `misc` is not a namespace of the search nor of the votes.
+        let namespace = match tag_group.namespace_opt() {
+            Some(namespace) => String::from(namespace.name()),
+            None => tag_group.tag_group_name.clone(),
+        };
+
+        // gt, gtl or gtw.
+        for ele in root.find("[class^=gt]") {
+            let mut tag = Tag::parse(&ele.outer_html())?;
+            if tag.namespace.is_empty() {
+                tag.namespace = namespace.clone();
+            }
+            tag_group.tag_vec.push(tag);
+        }
+
+        Ok(tag_group)
     }
 }
 
@@ -60,8 +85,10 @@ mod tests {
         "#;
 
         let tag_group = GalleryTagGroup::parse(&ele).unwrap();
-        assert_eq!(tag_group.tag_vec, vec![r#"senran kagura"#, r#"the idolmaster"#]);
+        let name_vec = tag_group.tag_vec.iter().map(|tag| tag.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(name_vec, vec![r#"senran kagura"#, r#"the idolmaster"#]);
         assert_eq!(tag_group.tag_group_name, "parody");
+        assert_eq!(tag_group.namespace_opt(), Some(Namespace::Parody));
 
         let ele = r#"
             <tr>
@@ -78,7 +105,22 @@ mod tests {
         "#;
 
         let tag_group = GalleryTagGroup::parse(&ele).unwrap();
-        assert_eq!(tag_group.tag_vec, vec!["chinese", "translated"]);
+        let name_vec = tag_group.tag_vec.iter().map(|tag| tag.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(name_vec, vec!["chinese", "translated"]);
+        assert_eq!(tag_group.tag_vec[0].id_opt.as_deref(), Some("language:chinese"));
         assert_eq!(tag_group.tag_group_name, "language");
+
+        let ele = r#"
+            <tr>
+                <td class="tc">misc:</td>
+                <td>
+                    <div class="gt" title="full color">full color</div>
+                </td>
+            </tr>
+        "#;
+
+        let tag_group = GalleryTagGroup::parse(ele).unwrap();
+        assert_eq!(tag_group.tag_group_name, "misc");
+        assert_eq!(tag_group.tag_vec[0].full_name(), "other:full color");
     }
 }
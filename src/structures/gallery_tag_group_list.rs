@@ -2,6 +2,7 @@ use visdom::Vis;
 use crate::{EhResult, Parser, structures::GalleryTagGroup};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GalleryTagGroupList {
     pub group_vec: Vec<GalleryTagGroup>,
 }
@@ -0,0 +1,226 @@
+use std::str::FromStr;
+use crate::{EhResult, ParseError};
+
+/// The languages the site tells apart, every other one is [`Language::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language {
+    Japanese,
+    English,
+    Chinese,
+    Dutch,
+    French,
+    German,
+    Hungarian,
+    Italian,
+    Korean,
+    Polish,
+    Portuguese,
+    Russian,
+    Spanish,
+    Thai,
+    Vietnamese,
+    /// `N/A`, like a gallery without text.
+    NotApplicable,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Origin {
+    Original,
+    /// `TR` on the detail page, `language:translated` in the tags.
+    Translated,
+    /// `RW` on the detail page, `language:rewrite` in the tags.
+    Rewrite,
+}
+
+/// A language with its origin, the pair the excluded languages of [`crate::structures::UConfig`] work on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GalleryLanguage {
+    pub language: Language,
+    pub origin: Origin,
+}
+
+impl Language {
+    /// The lowercase name of the `language:` tag.
+    pub fn name(&self) -> &'static str {
+        LANGUAGE_TABLE.iter().find(|(language, _, _)| language == self).map(|(_, name, _)| *name).unwrap_or_default()
+    }
+
+    /// The code of the original language, see [`GalleryLanguage::code`].
+    pub fn code(&self) -> u32 {
+        LANGUAGE_TABLE.iter().find(|(language, _, _)| language == self).map(|(_, _, code)| *code).unwrap_or_default()
+    }
+
+    /// Reads a `language:` tag, with or without the namespace.
+    /// `None` for the tags about the origin, like `language:translated`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let name = tag.strip_prefix("language:").unwrap_or(tag).trim().to_lowercase();
+        match name.as_str() {
+            TAG_TRANSLATED | TAG_REWRITE | TAG_TEXT_CLEANED => None,
+            TAG_SPEECHLESS => Some(Language::NotApplicable),
+            name => Some(name.parse().unwrap_or(Language::Other)),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ParseError;
+
+    /// The name of the language in any case, like `Japanese` or `N/A`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        LANGUAGE_TABLE.iter()
+            .find(|(_, name, _)| name.eq_ignore_ascii_case(s))
+            .map(|(language, _, _)| *language)
+            .ok_or_else(|| ParseError::from(format!("unknown language: {}", s)))
+    }
+}
+
+impl Origin {
+    pub fn offset(&self) -> u32 {
+        match self {
+            Origin::Original => 0,
+            Origin::Translated => ORIGIN_STEP,
+            Origin::Rewrite => ORIGIN_STEP * 2,
+        }
+    }
+}
+
+impl GalleryLanguage {
+    pub fn new(language: Language, origin: Origin) -> Self {
+        GalleryLanguage { language, origin }
+    }
+
+    /// The code of the excluded language setting, like `eh_config::CHINESE_TRANSLATED`.
+    pub fn code(&self) -> u32 {
+        self.language.code() + self.origin.offset()
+    }
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        let origin = match code / ORIGIN_STEP {
+            0 => Origin::Original,
+            1 => Origin::Translated,
+            2 => Origin::Rewrite,
+            _ => return None,
+        };
+        let code = code % ORIGIN_STEP;
+        LANGUAGE_TABLE.iter()
+            .find(|(_, _, base)| *base == code)
+            .map(|(language, _, _)| GalleryLanguage::new(*language, origin))
+    }
+
+    /// Reads the `language:` tags of a gallery, `None` without a language tag.
+    pub fn from_tag_vec<S: AsRef<str>>(tag_vec: &[S]) -> Option<Self> {
+        let mut language_opt = None;
+        let mut origin = Origin::Original;
+        for tag in tag_vec {
+            let tag = tag.as_ref();
+            if !tag.starts_with("language:") {
+                continue;
+            }
+            match tag.trim_start_matches("language:") {
+                TAG_TRANSLATED => origin = Origin::Translated,
+                TAG_REWRITE => origin = Origin::Rewrite,
+                _ => language_opt = language_opt.or_else(|| Language::from_tag(tag)),
+            }
+        }
+
+        language_opt.map(|language| GalleryLanguage::new(language, origin))
+    }
+
+    /// Reads the Language row of the detail page, `Chinese  TR`.
+    /// A language the site does not tell apart is [`Language::Other`].
+    pub fn from_detail(text: &str) -> EhResult<Self> {
+        let text = text.trim();
+        let (name, origin) = if let Some(name) = text.strip_suffix(FLAG_TRANSLATED) {
+            (name, Origin::Translated)
+        } else if let Some(name) = text.strip_suffix(FLAG_REWRITE) {
+            (name, Origin::Rewrite)
+        } else {
+            (text, Origin::Original)
+        };
+
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ParseError::from(format!("language not found: {}", text)));
+        }
+
+        Ok(GalleryLanguage::new(name.parse().unwrap_or(Language::Other), origin))
+    }
+}
+
+const ORIGIN_STEP: u32 = 1024;
+const FLAG_TRANSLATED: &str = "TR";
+const FLAG_REWRITE: &str = "RW";
+const TAG_TRANSLATED: &str = "translated";
+const TAG_REWRITE: &str = "rewrite";
+const TAG_TEXT_CLEANED: &str = "text cleaned";
+const TAG_SPEECHLESS: &str = "speechless";
+
+/// The language, its name and the code of the original, the codes of `eh_config`.
+const LANGUAGE_TABLE: [(Language, &str, u32); 17] = [
+    (Language::Japanese, "japanese", 0),
+    (Language::English, "english", 1),
+    (Language::Chinese, "chinese", 10),
+    (Language::Dutch, "dutch", 20),
+    (Language::French, "french", 30),
+    (Language::German, "german", 40),
+    (Language::Hungarian, "hungarian", 50),
+    (Language::Italian, "italian", 60),
+    (Language::Korean, "korean", 70),
+    (Language::Polish, "polish", 80),
+    (Language::Portuguese, "portuguese", 90),
+    (Language::Russian, "russian", 100),
+    (Language::Spanish, "spanish", 110),
+    (Language::Thai, "thai", 120),
+    (Language::Vietnamese, "vietnamese", 130),
+    (Language::NotApplicable, "n/a", 254),
+    (Language::Other, "other", 255),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::eh_config;
+    use super::*;
+
+    #[test]
+    fn code_test() {
+        let code = |language, origin| GalleryLanguage::new(language, origin).code().to_string();
+        assert_eq!(code(Language::Japanese, Origin::Original), eh_config::JAPANESE_ORIGINAL);
+        assert_eq!(code(Language::Chinese, Origin::Translated), eh_config::CHINESE_TRANSLATED);
+        assert_eq!(code(Language::Vietnamese, Origin::Rewrite), eh_config::VIETNAMESE_REWRITE);
+        assert_eq!(code(Language::NotApplicable, Origin::Translated), eh_config::NA_TRANSLATED);
+        assert_eq!(code(Language::Other, Origin::Rewrite), eh_config::OTHER_REWRITE);
+
+        for (language, _, _) in LANGUAGE_TABLE {
+            for origin in [Origin::Original, Origin::Translated, Origin::Rewrite] {
+                let gallery_language = GalleryLanguage::new(language, origin);
+                assert_eq!(GalleryLanguage::from_code(gallery_language.code()), Some(gallery_language));
+            }
+        }
+        assert_eq!(GalleryLanguage::from_code(3072), None);
+        assert_eq!(GalleryLanguage::from_code(5), None);
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(GalleryLanguage::from_detail("Japanese \u{a0}").unwrap(), GalleryLanguage::new(Language::Japanese, Origin::Original));
+        assert_eq!(GalleryLanguage::from_detail("Chinese \u{a0}TR").unwrap(), GalleryLanguage::new(Language::Chinese, Origin::Translated));
+        assert_eq!(GalleryLanguage::from_detail("English RW").unwrap(), GalleryLanguage::new(Language::English, Origin::Rewrite));
+        assert_eq!(GalleryLanguage::from_detail("N/A").unwrap(), GalleryLanguage::new(Language::NotApplicable, Origin::Original));
+        assert_eq!(GalleryLanguage::from_detail("Indonesian").unwrap(), GalleryLanguage::new(Language::Other, Origin::Original));
+        assert!(GalleryLanguage::from_detail(" ").is_err());
+
+        assert_eq!(Language::from_tag("language:korean"), Some(Language::Korean));
+        assert_eq!(Language::from_tag("language:translated"), None);
+        assert_eq!(Language::from_tag("language:speechless"), Some(Language::NotApplicable));
+        assert_eq!(
+            GalleryLanguage::from_tag_vec(&["female:glasses", "language:translated", "language:chinese"]),
+            Some(GalleryLanguage::new(Language::Chinese, Origin::Translated)),
+        );
+        assert_eq!(GalleryLanguage::from_tag_vec(&["female:glasses"]), None);
+    }
+}
@@ -1,3 +1,16 @@
+//! The structures the parsers produce.
+//!
+//! With the `serde` feature, every structure here derives `Serialize` and `Deserialize` so a parsed
+//! page can be cached or handed to another process. The schema follows the Rust definitions:
+//!
+//! - Fields keep their names, `_opt` fields are `null` when absent and `_vec` fields are arrays.
+//! - Enums are their variant name as a string, like `"Exclude"`, or `{"Hidden": "Deleted"}` for a variant with data.
+//! - Dates are RFC 3339 strings in UTC, like `"2023-02-07T07:33:00Z"`.
+//! - [`gallery_list::GalleryList`] is `{"mode": "Extended", "gallery_info_vec": [...]}`, [`gallery_detail::GalleryPreviewSet`] is
+//!   `{"kind": "Large", "preview_vec": [...]}` and [`SearchTarget`] is `{"target": "Favorites", "slot_opt": 2}`.
+//!
+//! Renaming a field or variant is a breaking change of the schema.
+
 mod archive;
 mod category;
 mod event_pane;
@@ -10,9 +23,11 @@ mod torrent;
 mod vote_comment;
 mod vote_tag;
 mod favorite_slot;
-mod gallery_multi_page_viewer_p_token;
+mod gallery_image;
+mod gallery_multi_page_viewer;
 mod gallery_not_available;
 mod gallery_page_api;
+mod gallery_page_mpv_api;
 mod gallery_page;
 mod gallery_page_url;
 mod gallery_token_api;
@@ -24,20 +39,32 @@ mod rating;
 mod gallery;
 mod gallery_api;
 mod search_nav;
+mod search_query;
+mod search_expression;
+mod uconfig;
+mod uconfig_page;
+mod my_tags;
+mod user_tag;
+mod language;
+mod tag;
+mod namespace;
 
 pub use {
     archive::Archive,
-    category::Category,
+    category::{Category, CategorySet},
     event_pane::EventPane,
     favorite_slot::FavoriteSlot,
     favorites::Favorite,
     forums::Forums,
     gallery::Gallery,
+    gallery_api::{GalleryApi, GalleryMetadata, GalleryMetadataError, GalleryMetadataTorrent},
     gallery_detail_url::GalleryDetailUrl,
-    gallery_multi_page_viewer_p_token::GalleryMultiPageViewerPToken,
+    gallery_image::GalleryImage,
+    gallery_multi_page_viewer::{GalleryMultiPageViewer, GalleryMultiPageViewerImage},
     gallery_not_available::GalleryNotAvailable,
     gallery_page::GalleryPage,
     gallery_page_api::GalleryPageApi,
+    gallery_page_mpv_api::GalleryPageMpvApi,
     gallery_page_url::GalleryPageUrl,
     gallery_tag_group::GalleryTagGroup,
     gallery_tag_group_list::GalleryTagGroupList,
@@ -51,6 +78,33 @@ pub use {
     vote_comment::VoteComment,
     vote_tag::VoteTag,
     search_nav::SearchNav,
+    search_query::{AdvancedSearch, SearchQuery, SearchTarget},
+    search_expression::{SearchExpression, SearchTerm, TermModifier},
+    uconfig::{
+        UConfig,
+        ArchiverDownload,
+        CommentSort,
+        CommentVotes,
+        FavoritesSort,
+        GalleryTitle,
+        ImageSize,
+        LayoutMode,
+        LoadFromHah,
+        LofiResolution,
+        MouseOver,
+        MultiPageStyle,
+        MultiPageThumb,
+        PreviewRow,
+        PreviewSize,
+        ResultCount,
+        TagSort,
+    },
+    uconfig_page::{UConfigPage, UConfigProfile},
+    my_tags::{MyTags, TagSet},
+    user_tag::UserTag,
+    language::{GalleryLanguage, Language, Origin},
+    tag::{Tag, TagPower, TagVote},
+    namespace::Namespace,
 };
 
 pub mod gallery_list;
@@ -0,0 +1,126 @@
+use visdom::Vis;
+use crate::{EhResult, ParseError, Parser, structures::UserTag};
+
+/// The My Tags page, showing one tag set at a time.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MyTags {
+    pub tagset_vec: Vec<TagSet>,
+    /// Whether the tags of the shown set are applied on the site.
+    pub enabled: bool,
+    /// The default color of the tags of the shown set without their own, like `#ff0000`.
+    pub color_opt: Option<String>,
+    pub user_tag_vec: Vec<UserTag>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TagSet {
+    pub id: u32,
+    pub name: String,
+    /// The set the page shows.
+    pub selected: bool,
+}
+
+impl MyTags {
+    pub fn selected_tagset(&self) -> Option<&TagSet> {
+        self.tagset_vec.iter().find(|tagset| tagset.selected)
+    }
+}
+
+impl Parser for MyTags {
+    /// ```html
+    /// <div id="tagset_outer">
+    ///     <select name="tagset_list"><option value="1" selected="selected">Default</option><option value="2">Set 2</option></select>
+    ///     <input type="checkbox" name="tagset_enable" id="tagset_enable" checked="checked" />
+    ///     <input type="text" name="tagset_color" id="tagset_color" value="" />
+    /// </div>
+    /// <div id="usertags_outer">
+    ///     <div id="usertag_0">...</div>
+    ///     <div id="usertag_12345">...</div>
+    /// </div>
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let root = Vis::load(doc)?;
+
+        let tagset_vec = root.find("select[name=tagset_list] option").map(|_, ele| {
+            let id = ele.get_attribute("value").map(|value| value.to_string()).unwrap_or_default();
+            (id, ele.text().trim().to_string(), ele.has_attribute("selected"))
+        });
+        if tagset_vec.is_empty() {
+            return Err(ParseError::from(String::from("tag set list not found")));
+        }
+
+        let mut tagset_vec = tagset_vec.into_iter()
+            .map(|(id, name, selected)| Ok(TagSet { id: id.parse()?, name, selected }))
+            .collect::<EhResult<Vec<TagSet>>>()?;
+        if !tagset_vec.iter().any(|tagset| tagset.selected) {
+            // no `selected` attribute, the browser shows the first one.
+            tagset_vec[0].selected = true;
+        }
+
+        let enabled = root.find("#tagset_enable").has_attr("checked");
+        let color = root.find("#tagset_color").val().to_string();
+        let color_opt = Some(color).filter(|color| !color.is_empty());
+
+        let mut user_tag_vec = Vec::new();
+        for user_tag in root.find("div[id^=usertag_]") {
+            // `usertag_0` holds the inputs for a new tag.
+            if user_tag.get_attribute("id").is_some_and(|id| id.is_str("usertag_0")) {
+                continue;
+            }
+            user_tag_vec.push(UserTag::parse(&user_tag.outer_html())?);
+        }
+
+        Ok(MyTags {
+            tagset_vec,
+            enabled,
+            color_opt,
+            user_tag_vec,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let doc = r##"
+<div id="tagset_outer">
+    <div><select name="tagset_list" onchange="do_tagset_select()"><option value="1">Default</option><option value="3" selected="selected">Artists</option></select></div>
+    <div><input type="checkbox" name="tagset_enable" id="tagset_enable" checked="checked" /><label for="tagset_enable">Show</label></div>
+    <div><input type="text" name="tagset_color" id="tagset_color" value="#0000ff" maxlength="7" /></div>
+</div>
+<div id="usertags_outer">
+    <div id="usertag_0"><input type="text" id="tagname_new" name="tagname_new" value="" /></div>
+    <div id="usertag_12345">
+        <div><div id="tagpreview_12345" class="gt" title="artist:xxxx">artist:xxxx</div></div>
+        <div><input type="checkbox" id="tagwatch_12345" checked="checked" /></div>
+        <div><input type="checkbox" id="taghide_12345" /></div>
+        <div><input type="text" id="tagcolor_12345" value="" /></div>
+        <div><input type="text" id="tagweight_12345" value="10" /></div>
+    </div>
+    <div id="usertag_12346">
+        <div><div id="tagpreview_12346" class="gt" title="male:guro">male:guro</div></div>
+        <div><input type="checkbox" id="tagwatch_12346" /></div>
+        <div><input type="checkbox" id="taghide_12346" checked="checked" /></div>
+        <div><input type="text" id="tagcolor_12346" value="" /></div>
+        <div><input type="text" id="tagweight_12346" value="10" /></div>
+    </div>
+</div>
+"##;
+        let my_tags = MyTags::parse(doc).unwrap();
+        assert_eq!(my_tags.tagset_vec.len(), 2);
+        assert_eq!(my_tags.selected_tagset(), Some(&TagSet { id: 3, name: String::from("Artists"), selected: true }));
+        assert!(my_tags.enabled);
+        assert_eq!(my_tags.color_opt.as_deref(), Some("#0000ff"));
+        assert_eq!(my_tags.user_tag_vec.len(), 2);
+        assert_eq!(my_tags.user_tag_vec[0], UserTag { id: 12345, ..UserTag::new("artist:xxxx") });
+        assert!(my_tags.user_tag_vec[1].hidden);
+        assert!(!my_tags.user_tag_vec[1].watched);
+
+        assert!(MyTags::parse("<html><body></body></html>").is_err());
+    }
+}
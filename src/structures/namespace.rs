@@ -0,0 +1,134 @@
+use std::{fmt::{Display, Formatter}, str::FromStr};
+use crate::{eh_config::*, ParseError};
+
+/// A tag namespace, written in full in the tag lists, as an alias in the searches
+/// and as a bit in the excluded namespaces of [`crate::structures::UConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Namespace {
+    Reclass,
+    Language,
+    Parody,
+    Character,
+    Group,
+    Artist,
+    Cosplayer,
+    Male,
+    Female,
+    Mixed,
+    /// `misc` in the tag list of the older pages, see [`crate::structures::GalleryTagGroup::namespace_opt`].
+    Other,
+    Location,
+    Temp,
+}
+
+impl Namespace {
+    pub const ALL: [Namespace; 13] = [
+        Namespace::Reclass,
+        Namespace::Language,
+        Namespace::Parody,
+        Namespace::Character,
+        Namespace::Group,
+        Namespace::Artist,
+        Namespace::Cosplayer,
+        Namespace::Male,
+        Namespace::Female,
+        Namespace::Mixed,
+        Namespace::Other,
+        Namespace::Location,
+        Namespace::Temp,
+    ];
+
+    /// Like `female`.
+    pub fn name(&self) -> &'static str {
+        self.entry().1
+    }
+
+    /// The short form of the search, like `f`.
+    pub fn alias(&self) -> &'static str {
+        self.entry().2
+    }
+
+    /// The bit of the excluded namespaces, `None` for the namespaces the setting does not offer.
+    pub fn mask_opt(&self) -> Option<u32> {
+        Some(self.entry().3).filter(|mask| *mask != 0)
+    }
+
+    /// The mask of the excluded namespaces, the namespaces without a bit are left out.
+    pub fn to_mask(namespace_vec: &[Namespace]) -> u32 {
+        namespace_vec.iter().filter_map(|namespace| namespace.mask_opt()).fold(0, |mask, bit| mask | bit)
+    }
+
+    pub fn from_mask(mask: u32) -> Vec<Namespace> {
+        Namespace::ALL.into_iter()
+            .filter(|namespace| namespace.mask_opt().is_some_and(|bit| mask & bit != 0))
+            .collect()
+    }
+
+    fn entry(&self) -> &'static (Namespace, &'static str, &'static str, u32) {
+        // every variant has its row.
+        NAMESPACE_TABLE.iter().find(|(namespace, _, _, _)| namespace == self).unwrap()
+    }
+}
+
+impl Display for Namespace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Namespace {
+    type Err = ParseError;
+
+    /// The name or the alias in any case, with or without the colon.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_suffix(':').unwrap_or(s).to_lowercase();
+
+        NAMESPACE_TABLE.iter()
+            .find(|(_, name, alias, _)| *name == s || *alias == s)
+            .map(|(namespace, _, _, _)| *namespace)
+            .ok_or_else(|| ParseError::from(format!("unknown namespace: {}", s)))
+    }
+}
+
+/// The namespace, its name, its alias and its bit, 0 for none.
+const NAMESPACE_TABLE: [(Namespace, &str, &str, u32); 13] = [
+    (Namespace::Reclass, "reclass", "r", NAMESPACES_RECLASS),
+    (Namespace::Language, "language", "l", NAMESPACES_LANGUAGE),
+    (Namespace::Parody, "parody", "p", NAMESPACES_PARODY),
+    (Namespace::Character, "character", "c", NAMESPACES_CHARACTER),
+    (Namespace::Group, "group", "g", NAMESPACES_GROUP),
+    (Namespace::Artist, "artist", "a", NAMESPACES_ARTIST),
+    (Namespace::Cosplayer, "cosplayer", "cos", 0),
+    (Namespace::Male, "male", "m", NAMESPACES_MALE),
+    (Namespace::Female, "female", "f", NAMESPACES_FEMALE),
+    (Namespace::Mixed, "mixed", "x", 0),
+    (Namespace::Other, "other", "o", 0),
+    (Namespace::Location, "location", "loc", 0),
+    (Namespace::Temp, "temp", "temp", 0),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_test() {
+        assert_eq!("f".parse::<Namespace>().unwrap(), Namespace::Female);
+        assert_eq!("Parody:".parse::<Namespace>().unwrap(), Namespace::Parody);
+        assert_eq!("cos".parse::<Namespace>().unwrap(), Namespace::Cosplayer);
+        assert!("uploader".parse::<Namespace>().is_err());
+
+        for namespace in Namespace::ALL {
+            assert_eq!(namespace.name().parse::<Namespace>().unwrap(), namespace);
+            assert_eq!(namespace.alias().parse::<Namespace>().unwrap(), namespace);
+        }
+
+        assert_eq!(Namespace::Male.mask_opt(), Some(NAMESPACES_MALE));
+        assert_eq!(Namespace::Location.mask_opt(), None);
+        let mask = Namespace::to_mask(&[Namespace::Parody, Namespace::Male, Namespace::Mixed]);
+        assert_eq!(mask, NAMESPACES_PARODY | NAMESPACES_MALE);
+        assert_eq!(Namespace::from_mask(mask), vec![Namespace::Parody, Namespace::Male]);
+    }
+}
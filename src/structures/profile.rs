@@ -2,6 +2,7 @@ use visdom::Vis;
 use crate::{ATTRIBUTE_NOT_FOUND, eh_url, EhResult, Parser};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Profile {
     pub display_name: String,
     pub avatar: String,
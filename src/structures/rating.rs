@@ -2,6 +2,7 @@ use regex::Regex;
 use crate::{EhResult, Parser, REGEX_MATCH_FAILED};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rating {
     pub value: f32,
 }
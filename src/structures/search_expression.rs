@@ -0,0 +1,236 @@
+use std::fmt::{Display, Formatter};
+use crate::{EhResult, ParseError, Parser, structures::Namespace};
+
+/// `f_search` split into terms.
+/// ```text
+/// female:"big breasts$" -m:futanari ~l:chinese$ ~l:english$ uploader:xxxx "the idolmaster"
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchExpression {
+    pub term_vec: Vec<SearchTerm>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TermModifier {
+    None,
+    /// `-`, must not match.
+    Exclude,
+    /// `~`, at least one of the `~` terms must match.
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchTerm {
+    pub modifier: TermModifier,
+    /// As written, `f` stays `f`, see [`SearchExpression::normalized`].
+    /// `uploader` and `title` are pseudo namespaces.
+    pub namespace_opt: Option<String>,
+    pub text: String,
+    /// Wrapped in `"`, needed when `text` contains spaces.
+    pub quoted: bool,
+    /// Ends with `$`, the tag must match exactly.
+    pub exact: bool,
+}
+
+impl SearchTerm {
+    pub fn new(namespace_opt: Option<&str>, text: &str) -> Self {
+        SearchTerm {
+            modifier: TermModifier::None,
+            namespace_opt: namespace_opt.map(String::from),
+            text: String::from(text),
+            quoted: text.contains(char::is_whitespace),
+            exact: false,
+        }
+    }
+
+    pub fn is_pseudo(&self) -> bool {
+        // like the namespaces, in any case.
+        self.namespace_opt.as_deref().is_some_and(|namespace| {
+            namespace.eq_ignore_ascii_case(PSEUDO_UPLOADER) || namespace.eq_ignore_ascii_case(PSEUDO_TITLE)
+        })
+    }
+}
+
+impl Display for SearchTerm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.modifier {
+            TermModifier::None => {}
+            TermModifier::Exclude => write!(f, "-")?,
+            TermModifier::Or => write!(f, "~")?,
+        }
+
+        if let Some(namespace) = &self.namespace_opt {
+            write!(f, "{}:", namespace)?;
+        }
+
+        let exact = if self.exact { "$" } else { "" };
+        if self.quoted {
+            write!(f, "\"{}{}\"", self.text, exact)
+        } else {
+            write!(f, "{}{}", self.text, exact)
+        }
+    }
+}
+
+impl Display for SearchExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, term) in self.term_vec.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", term)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SearchExpression {
+    /// Rewrites the namespace aliases to their full names, `f:` becomes `female:`.
+    /// Fails on a namespace the site does not know.
+    pub fn normalized(&self) -> EhResult<Self> {
+        let mut term_vec = Vec::with_capacity(self.term_vec.len());
+        for term in &self.term_vec {
+            let mut term = term.clone();
+            if let (Some(namespace), false) = (&term.namespace_opt, term.is_pseudo()) {
+                term.namespace_opt = Some(String::from(namespace.parse::<Namespace>()?.name()));
+            }
+            term_vec.push(term);
+        }
+
+        Ok(SearchExpression { term_vec })
+    }
+}
+
+impl Parser for SearchExpression {
+    /// Printing the result with `to_string` gives the input back, with the terms separated by a single space.
+    /// A `$` after the closing quote is moved inside it, which is where the site puts it.
+    fn parse(doc: &str) -> EhResult<Self> {
+        let chars: Vec<char> = doc.chars().collect();
+        let mut term_vec = Vec::new();
+
+        let mut idx = 0;
+        while idx < chars.len() {
+            if chars[idx].is_whitespace() {
+                idx += 1;
+                continue;
+            }
+
+            let modifier = match chars[idx] {
+                '-' => TermModifier::Exclude,
+                '~' => TermModifier::Or,
+                _ => TermModifier::None,
+            };
+            if modifier != TermModifier::None {
+                idx += 1;
+            }
+
+            // namespace, letters before a colon.
+            let mut namespace_opt = None;
+            let bgn = idx;
+            while idx < chars.len() && chars[idx].is_ascii_alphabetic() {
+                idx += 1;
+            }
+            if idx < chars.len() && chars[idx] == ':' && bgn < idx {
+                namespace_opt = Some(chars[bgn..idx].iter().collect::<String>());
+                idx += 1;
+            } else {
+                idx = bgn;
+            }
+
+            let (mut text, quoted) = if idx < chars.len() && chars[idx] == '"' {
+                let end = chars[idx + 1..].iter()
+                    .position(|&c| c == '"')
+                    .map(|pos| idx + 1 + pos)
+                    .ok_or_else(|| ParseError::from(format!("unterminated quote at {}", idx)))?;
+                let text = chars[idx + 1..end].iter().collect::<String>();
+                idx = end + 1;
+                (text, true)
+            } else {
+                let bgn = idx;
+                while idx < chars.len() && !chars[idx].is_whitespace() {
+                    idx += 1;
+                }
+                (chars[bgn..idx].iter().collect::<String>(), false)
+            };
+
+            let mut exact = false;
+            if text.ends_with('$') {
+                text.pop();
+                exact = true;
+            }
+            if quoted && idx < chars.len() && chars[idx] == '$' {
+                exact = true;
+                idx += 1;
+            }
+
+            if text.is_empty() && namespace_opt.is_none() {
+                return Err(ParseError::from(format!("empty term at {}", bgn)));
+            }
+
+            term_vec.push(SearchTerm {
+                modifier,
+                namespace_opt,
+                text,
+                quoted,
+                exact,
+            });
+        }
+
+        Ok(SearchExpression { term_vec })
+    }
+}
+
+const PSEUDO_UPLOADER: &str = "uploader";
+const PSEUDO_TITLE: &str = "title";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let doc = r#"female:"big breasts$" -m:futanari ~l:chinese$ ~l:english$ uploader:xxxx "the idolmaster" touhou"#;
+        let expression = SearchExpression::parse(doc).unwrap();
+
+        assert_eq!(expression.term_vec.len(), 7);
+        assert_eq!(expression.term_vec[0], SearchTerm {
+            modifier: TermModifier::None,
+            namespace_opt: Some(String::from("female")),
+            text: String::from("big breasts"),
+            quoted: true,
+            exact: true,
+        });
+        assert_eq!(expression.term_vec[1].modifier, TermModifier::Exclude);
+        assert_eq!(expression.term_vec[2].modifier, TermModifier::Or);
+        assert!(expression.term_vec[4].is_pseudo());
+        assert_eq!(expression.term_vec[5].namespace_opt, None);
+        assert_eq!(expression.to_string(), doc);
+
+        let expression = SearchExpression::parse(r#"  f:"big breasts"$   -"futa"  "#).unwrap();
+        assert_eq!(expression.to_string(), r#"f:"big breasts$" -"futa""#);
+
+        assert!(SearchExpression::parse(r#"f:"big breasts"#).is_err());
+    }
+
+    #[test]
+    fn normalized_test() {
+        let expression = SearchExpression::parse(r#"f:"big breasts$" -M:futanari cos:xxxx title:"f:abc" x:group"#).unwrap();
+        assert_eq!(
+            expression.normalized().unwrap().to_string(),
+            r#"female:"big breasts$" -male:futanari cosplayer:xxxx title:"f:abc" mixed:group"#
+        );
+
+        let expression = SearchExpression::parse(r#"Uploader:foo TITLE:"x""#).unwrap();
+        assert_eq!(expression.normalized().unwrap().to_string(), r#"Uploader:foo TITLE:"x""#);
+
+        let expression = SearchExpression::parse("misc:xxxx").unwrap();
+        assert!(expression.normalized().is_err());
+
+        let term = SearchTerm::new(Some("parody"), "the idolmaster");
+        assert_eq!(term.to_string(), r#"parody:"the idolmaster""#);
+    }
+}
@@ -3,6 +3,7 @@ use visdom::Vis;
 use crate::{ATTRIBUTE_NOT_FOUND, EhResult, Parser, REGEX_MATCH_FAILED};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SearchNav {
     /// First page, value is `None`
     /// 1. Gallery: ?prev=2453492
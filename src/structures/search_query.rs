@@ -0,0 +1,347 @@
+use regex::Regex;
+use crate::{
+    const_concat,
+    eh_url::{self, Site},
+    EhResult,
+    Parser,
+    REGEX_MATCH_FAILED,
+    request::Request,
+    structures::{CategorySet, SearchNav},
+    url_encoding::decode_pairs,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "target", content = "slot_opt"))]
+pub enum SearchTarget {
+    /// The front page.
+    Front,
+    Watched,
+    /// Ignores every parameter.
+    Popular,
+    /// `None` for all the slots, only the keyword and the cursor apply.
+    Favorites(Option<u32>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchQuery {
+    pub site: Site,
+    pub target: SearchTarget,
+    /// f_search
+    pub keyword_opt: Option<String>,
+    /// Categories to show, the site takes the inverse in `f_cats`.
+    pub category: CategorySet,
+    pub advanced_opt: Option<AdvancedSearch>,
+    /// The cursors of [`SearchNav`].
+    pub prev_opt: Option<String>,
+    pub next_opt: Option<String>,
+    pub jump_opt: Option<String>,
+    pub seek_opt: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdvancedSearch {
+    /// f_sh
+    pub browse_expunged: bool,
+    /// f_sto
+    pub require_torrent: bool,
+    /// f_spf
+    pub min_pages_opt: Option<u32>,
+    /// f_spt
+    pub max_pages_opt: Option<u32>,
+    /// f_srdd, from 2 to 5 stars.
+    pub min_rating_opt: Option<u32>,
+    /// f_sfl
+    pub disable_language_filter: bool,
+    /// f_sfu
+    pub disable_uploader_filter: bool,
+    /// f_sft
+    pub disable_tag_filter: bool,
+}
+
+impl SearchQuery {
+    pub fn new(site: Site, target: SearchTarget) -> Self {
+        SearchQuery {
+            site,
+            target,
+            keyword_opt: None,
+            category: CategorySet::ALL,
+            advanced_opt: None,
+            prev_opt: None,
+            next_opt: None,
+            jump_opt: None,
+            seek_opt: None,
+        }
+    }
+
+    pub fn keyword(mut self, keyword: &str) -> Self {
+        self.keyword_opt = Some(String::from(keyword));
+        self
+    }
+
+    pub fn category(mut self, category: CategorySet) -> Self {
+        self.category = category;
+        self
+    }
+
+    pub fn advanced(mut self, advanced: AdvancedSearch) -> Self {
+        self.advanced_opt = Some(advanced);
+        self
+    }
+
+    /// `1d`, `3d`, `1w`, `2w`, `1m`, `6m`, `1y` or `2y` from the current cursor.
+    pub fn jump(mut self, jump: &str) -> Self {
+        self.jump_opt = Some(String::from(jump));
+        self.seek_opt = None;
+        self
+    }
+
+    /// `2023-02-01` from the current cursor.
+    pub fn seek(mut self, seek: &str) -> Self {
+        self.seek_opt = Some(String::from(seek));
+        self.jump_opt = None;
+        self
+    }
+
+    /// The page after the one `search_nav` belongs to, `None` on the last page.
+    pub fn next_page(&self, search_nav: &SearchNav) -> Option<Self> {
+        let next = search_nav.next_opt.as_ref()?;
+        Some(self.with_cursor(None, Some(next.clone())))
+    }
+
+    /// The page before the one `search_nav` belongs to, `None` on the first page.
+    pub fn prev_page(&self, search_nav: &SearchNav) -> Option<Self> {
+        let prev = search_nav.prev_opt.as_ref()?;
+        Some(self.with_cursor(Some(prev.clone()), None))
+    }
+
+    fn with_cursor(&self, prev_opt: Option<String>, next_opt: Option<String>) -> Self {
+        SearchQuery {
+            prev_opt,
+            next_opt,
+            jump_opt: None,
+            seek_opt: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn to_request(&self) -> Request {
+        let mut request = match self.target {
+            SearchTarget::Front => Request::get(self.site.host()),
+            SearchTarget::Watched => Request::get(self.site.watched()),
+            SearchTarget::Popular => return Request::get(self.site.popular()),
+            SearchTarget::Favorites(slot_opt) => {
+                let request = Request::get(self.site.favorites());
+                match slot_opt {
+                    Some(slot) => request.query("favcat", &slot.to_string()),
+                    None => request,
+                }
+            }
+        };
+
+        let is_favorites = matches!(self.target, SearchTarget::Favorites(_));
+        if !is_favorites && self.category != CategorySet::ALL {
+            request = request.query("f_cats", &self.category.to_f_cats().to_string());
+        }
+
+        if let Some(keyword) = &self.keyword_opt {
+            request = request.query("f_search", keyword);
+        }
+
+        if let (false, Some(advanced)) = (is_favorites, &self.advanced_opt) {
+            request = request.query("advsearch", "1");
+
+            let flags = [
+                ("f_sh", advanced.browse_expunged),
+                ("f_sto", advanced.require_torrent),
+                ("f_sfl", advanced.disable_language_filter),
+                ("f_sfu", advanced.disable_uploader_filter),
+                ("f_sft", advanced.disable_tag_filter),
+            ];
+            for (key, _) in flags.iter().filter(|(_, on)| *on) {
+                request = request.query(key, "on");
+            }
+
+            let numbers = [
+                ("f_spf", advanced.min_pages_opt),
+                ("f_spt", advanced.max_pages_opt),
+                ("f_srdd", advanced.min_rating_opt),
+            ];
+            for (key, number_opt) in numbers {
+                if let Some(number) = number_opt {
+                    request = request.query(key, &number.to_string());
+                }
+            }
+        }
+
+        let cursors = [
+            ("prev", &self.prev_opt),
+            ("next", &self.next_opt),
+            ("jump", &self.jump_opt),
+            ("seek", &self.seek_opt),
+        ];
+        for (key, cursor_opt) in cursors {
+            if let Some(cursor) = cursor_opt {
+                request = request.query(key, cursor);
+            }
+        }
+
+        request
+    }
+
+    pub fn to_url(&self) -> String {
+        self.to_request().full_url()
+    }
+}
+
+impl Parser for SearchQuery {
+    /// ```text
+    /// https://e-hentai.org/?f_cats=1017&f_search=female%3A%22big+breasts%24%22&advsearch=1&f_srdd=4&next=2458743&jump=1d
+    /// https://exhentai.org/watched
+    /// https://e-hentai.org/favorites.php?favcat=2&f_search=touhou
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let regex = Regex::new(URL_STRICT_PATTERN).unwrap();
+        let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
+
+        let site = if &captures[1] == eh_url::DOMAIN_EX { Site::Ex } else { Site::E };
+        let pairs = captures.get(3).map(|m| decode_pairs(m.as_str())).unwrap_or_default();
+        let value_opt = |key: &str| {
+            pairs.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        let flag = |key: &str| value_opt(key).is_some_and(|v| !v.is_empty());
+        let number_opt = |key: &str| -> EhResult<Option<u32>> {
+            match value_opt(key) {
+                Some(v) if !v.is_empty() => Ok(Some(v.parse()?)),
+                _ => Ok(None),
+            }
+        };
+
+        let target = match &captures[2] {
+            "watched" => SearchTarget::Watched,
+            "popular" => SearchTarget::Popular,
+            "favorites.php" => SearchTarget::Favorites(number_opt("favcat")?),
+            _ => SearchTarget::Front,
+        };
+
+        let category = match number_opt("f_cats")? {
+            Some(f_cats) => CategorySet::from_f_cats(f_cats),
+            None => CategorySet::ALL,
+        };
+
+        let is_advanced = value_opt("advsearch").is_some()
+            || pairs.iter().any(|(k, _)| ADVANCED_KEYS.contains(&k.as_str()));
+        let advanced_opt = if is_advanced {
+            Some(AdvancedSearch {
+                browse_expunged: flag("f_sh"),
+                require_torrent: flag("f_sto"),
+                min_pages_opt: number_opt("f_spf")?,
+                max_pages_opt: number_opt("f_spt")?,
+                min_rating_opt: number_opt("f_srdd")?,
+                disable_language_filter: flag("f_sfl"),
+                disable_uploader_filter: flag("f_sfu"),
+                disable_tag_filter: flag("f_sft"),
+            })
+        } else {
+            None
+        };
+
+        let string_opt = |key: &str| value_opt(key).filter(|v| !v.is_empty()).map(String::from);
+
+        Ok(SearchQuery {
+            site,
+            target,
+            keyword_opt: string_opt("f_search"),
+            category,
+            advanced_opt,
+            prev_opt: string_opt("prev"),
+            next_opt: string_opt("next"),
+            jump_opt: string_opt("jump"),
+            seek_opt: string_opt("seek"),
+        })
+    }
+}
+
+const URL_STRICT_PATTERN: &str = const_concat!("^https?://(", eh_url::DOMAIN_EX, "|", eh_url::DOMAIN_E, ")/(watched|popular|favorites.php|)/?(?:\\?([^#]*))?(?:#.*)?$");
+const ADVANCED_KEYS: [&str; 8] = ["f_sh", "f_sto", "f_spf", "f_spt", "f_srdd", "f_sfl", "f_sfu", "f_sft"];
+
+#[cfg(test)]
+mod tests {
+    use crate::eh_config;
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let url = "https://e-hentai.org/?f_cats=1017&f_search=female%3A%22big+breasts%24%22&advsearch=1&f_sh=on&f_spf=10&f_spt=&f_srdd=4&next=2458743&jump=1d";
+        let query = SearchQuery::parse(url).unwrap();
+
+        assert_eq!(query, SearchQuery {
+            site: Site::E,
+            target: SearchTarget::Front,
+            keyword_opt: Some(String::from(r#"female:"big breasts$""#)),
+            category: CategorySet::new(eh_config::DOUJINSHI | eh_config::MANGA),
+            advanced_opt: Some(AdvancedSearch {
+                browse_expunged: true,
+                min_pages_opt: Some(10),
+                min_rating_opt: Some(4),
+                ..Default::default()
+            }),
+            prev_opt: None,
+            next_opt: Some(String::from("2458743")),
+            jump_opt: Some(String::from("1d")),
+            seek_opt: None,
+        });
+
+        let query = SearchQuery::parse("https://exhentai.org/favorites.php?favcat=2&f_search=touhou").unwrap();
+        assert_eq!(query.site, Site::Ex);
+        assert_eq!(query.target, SearchTarget::Favorites(Some(2)));
+        assert_eq!(query.keyword_opt.as_deref(), Some("touhou"));
+
+        assert!(SearchQuery::parse("https://e-hentai.org/g/2455981/acc72caed0/").is_err());
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let query = SearchQuery::new(Site::Ex, SearchTarget::Watched)
+            .keyword("language:chinese$ -guro")
+            .category(CategorySet::new(eh_config::MANGA | eh_config::NON_H))
+            .advanced(AdvancedSearch {
+                require_torrent: true,
+                max_pages_opt: Some(200),
+                ..Default::default()
+            })
+            .seek("2023-02-01");
+
+        let url = query.to_url();
+        assert_eq!(url, "https://exhentai.org/watched?f_cats=763&f_search=language%3Achinese%24+-guro&advsearch=1&f_sto=on&f_spt=200&seek=2023-02-01");
+        assert_eq!(SearchQuery::parse(&url).unwrap(), query);
+
+        let search_nav = SearchNav {
+            prev_opt: Some(String::from("2458771")),
+            next_opt: None,
+            jump_opt: None,
+            seek_opt: None,
+        };
+        assert_eq!(query.next_page(&search_nav), None);
+        assert_eq!(query.prev_page(&search_nav).unwrap().to_url(), "https://exhentai.org/watched?f_cats=763&f_search=language%3Achinese%24+-guro&advsearch=1&f_sto=on&f_spt=200&prev=2458771");
+
+        let query = SearchQuery::new(Site::E, SearchTarget::Popular).keyword("ignored");
+        assert_eq!(query.to_url(), "https://e-hentai.org/popular");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let query = SearchQuery::new(Site::E, SearchTarget::Favorites(Some(2))).keyword("touhou");
+        let json = serde_json::to_value(&query).unwrap();
+        assert_eq!(json["site"], "E");
+        assert_eq!(json["target"], serde_json::json!({ "target": "Favorites", "slot_opt": 2 }));
+        assert_eq!(json["keyword_opt"], "touhou");
+        assert_eq!(json["advanced_opt"], serde_json::Value::Null);
+        assert_eq!(serde_json::from_value::<SearchQuery>(json).unwrap(), query);
+    }
+}
@@ -2,6 +2,7 @@ use regex::Regex;
 use crate::{EhResult, ParseError, Parser, REGEX_MATCH_FAILED};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignIn {
     pub username: String,
 }
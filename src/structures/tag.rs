@@ -0,0 +1,170 @@
+use regex::Regex;
+use visdom::Vis;
+use crate::{DOM_NOT_FOUND, EhResult, Parser, structures::Namespace};
+
+/// A tag of the tag list on the detail page.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tag {
+    /// Empty when the markup does not tell, [`crate::structures::GalleryTagGroup`] fills it from its row.
+    pub namespace: String,
+    pub name: String,
+    /// `parody:the_idolmaster` from `td_parody:the_idolmaster`, `None` in the markup without ids.
+    pub id_opt: Option<String>,
+    pub power: TagPower,
+    /// From 0.0 to 1.0, the site fades the tags voted down.
+    pub opacity: f32,
+    /// The vote of the signed in user.
+    pub vote: TagVote,
+}
+
+/// From the class of the tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TagPower {
+    /// `gt`, solid border.
+    Strong,
+    /// `gtl`, dashed border.
+    Weak,
+    /// `gtw`, dotted border.
+    LowPower,
+}
+
+/// From the class of the anchor, `tup` or `tdn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TagVote {
+    None,
+    Up,
+    Down,
+}
+
+impl Tag {
+    /// `namespace:name`, the form the tag search and the tag voting take.
+    pub fn full_name(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}:{}", self.namespace, self.name)
+        }
+    }
+
+    /// `None` for a namespace newer than [`Namespace`].
+    pub fn namespace_opt(&self) -> Option<Namespace> {
+        self.namespace.parse().ok()
+    }
+}
+
+impl Parser for Tag {
+    /// ```html
+    /// <div class="gtl" title="parody:senran kagura">senran kagura</div>
+    /// ```
+    /// Or
+    /// ```html
+    /// <div id="td_parody:the_idolmaster" class="gtl" style="opacity:1.0"><a id="ta_parody:the_idolmaster" href="https://e-hentai.org/tag/parody:the+idolmaster" class="tup" onclick="return toggle_tagmenu('parody:the idolmaster',this)">the idolmaster</a></div>
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let root = Vis::load(doc)?;
+        let div = root.find("[class^=gt]");
+        if div.is_empty() {
+            return Err(DOM_NOT_FOUND);
+        }
+        let a = div.find("a");
+
+        let id_opt = div.attr("id")
+            .and_then(|id| id.to_string().strip_prefix("td_").map(String::from));
+
+        // the title, then the argument of the menu, then the id with its spaces back.
+        let regex = Regex::new(PATTERN_TAG_MENU).unwrap();
+        let onclick = a.attr("onclick").map(|onclick| onclick.to_string()).unwrap_or_default();
+        let full_name = match div.attr("title") {
+            Some(title) => title.to_string(),
+            None => match regex.captures(&onclick) {
+                Some(captures) => captures[1].to_string(),
+                None => match &id_opt {
+                    Some(id) => id.replace('_', " "),
+                    None => div.text().split_whitespace().collect::<Vec<&str>>().join(" "),
+                },
+            },
+        };
+        let (namespace, name) = match full_name.split_once(':') {
+            Some((namespace, name)) => (namespace.to_string(), name.to_string()),
+            None => (String::new(), full_name),
+        };
+
+        let power = if div.has_class("gtl") {
+            TagPower::Weak
+        } else if div.has_class("gtw") {
+            TagPower::LowPower
+        } else {
+            TagPower::Strong
+        };
+
+        let style = div.attr("style").map(|style| style.to_string()).unwrap_or_default();
+        let regex = Regex::new(PATTERN_OPACITY).unwrap();
+        let opacity = match regex.captures(&style) {
+            Some(captures) => captures[1].parse()?,
+            None => 1.0,
+        };
+
+        let vote = if a.has_class("tup") {
+            TagVote::Up
+        } else if a.has_class("tdn") {
+            TagVote::Down
+        } else {
+            TagVote::None
+        };
+
+        Ok(Tag {
+            namespace,
+            name,
+            id_opt,
+            power,
+            opacity,
+            vote,
+        })
+    }
+}
+
+const PATTERN_TAG_MENU: &str = r#"toggle_tagmenu\('(.+?)',"#;
+const PATTERN_OPACITY: &str = r#"opacity:\s*([\d.]+)"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let ele = r#"<div class="gtl" title="parody:senran kagura">senran kagura</div>"#;
+        assert_eq!(Tag::parse(ele).unwrap(), Tag {
+            namespace: String::from("parody"),
+            name: String::from("senran kagura"),
+            id_opt: None,
+            power: TagPower::Weak,
+            opacity: 1.0,
+            vote: TagVote::None,
+        });
+
+        let ele = r#"
+            <div id="td_parody:dragon_quest_vii" class="gtw" style="opacity:0.6"><a
+                    id="ta_parody:dragon_quest_vii" href="https://e-hentai.org/tag/parody:dragon+quest+vii"
+                    class="tdn" onclick="return toggle_tagmenu('parody:dragon quest vii',this)">dragon quest
+                    vii</a></div>
+        "#;
+        let tag = Tag::parse(ele).unwrap();
+        assert_eq!(tag.full_name(), "parody:dragon quest vii");
+        assert_eq!(tag.namespace_opt(), Some(Namespace::Parody));
+        assert_eq!(tag.id_opt.as_deref(), Some("parody:dragon_quest_vii"));
+        assert_eq!(tag.power, TagPower::LowPower);
+        assert_eq!(tag.opacity, 0.6);
+        assert_eq!(tag.vote, TagVote::Down);
+
+        let ele = r#"<div id="td_full_color" class="gt" style="opacity:1.0"><a id="ta_full_color" class="tup">full color</a></div>"#;
+        let tag = Tag::parse(ele).unwrap();
+        assert_eq!(tag.full_name(), "full color");
+        assert_eq!(tag.power, TagPower::Strong);
+        assert_eq!(tag.vote, TagVote::Up);
+
+        assert!(Tag::parse("<div></div>").is_err());
+    }
+}
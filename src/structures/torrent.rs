@@ -1,7 +1,8 @@
 use regex::Regex;
-use crate::{EhResult, Parser, REGEX_MATCH_FAILED};
+use crate::{EhResult, Parser, REGEX_MATCH_FAILED, page_kind::pre_check};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Torrent {
     pub filename: String,
     pub download_url: String,
@@ -9,6 +10,8 @@ pub struct Torrent {
 
 impl Parser for Torrent {
     fn parse(doc: &str) -> EhResult<Self> {
+        pre_check(doc)?;
+
         let regex = Regex::new(PATTERN_TORRENT).unwrap();
         let captures = regex.captures(doc).ok_or(REGEX_MATCH_FAILED)?;
 
@@ -40,5 +43,8 @@ mod tests {
             filename: String::from(r#"xxxx.zip"#),
             download_url: String::from("https://ehtracker.org/get/xxxx/xxxx.torrent?p=xxxx"),
         });
+
+        let doc = "Your IP address has been temporarily banned for excessive pageloads. The ban expires in 1 hour";
+        assert!(matches!(Torrent::parse(doc), Err(crate::ParseError::IpBanned(_))));
     }
 }
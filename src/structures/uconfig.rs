@@ -0,0 +1,434 @@
+use std::{fmt::{Display, Formatter}, str::FromStr};
+use crate::{eh_config::*, ParseError, structures::{GalleryLanguage, Namespace}};
+
+/// Declares a setting whose values are the `eh_config` constants.
+/// The variants follow the order of the options on uconfig.php.
+macro_rules! uconfig_enum {
+    ($(#[$meta:meta])* $name:ident { $($(#[$variant_meta:meta])* $variant:ident => $value:expr,)+ }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+        }
+
+        impl $name {
+            /// The value in the uconfig cookie.
+            pub fn value(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $value,)+
+                }
+            }
+
+            pub fn from_value(value: &str) -> Option<Self> {
+                $(if value == $value {
+                    return Some($name::$variant);
+                })+
+
+                None
+            }
+
+            /// The value on uconfig.php, the index of the option.
+            pub fn form_value(&self) -> String {
+                let idx = [$($name::$variant,)+].iter().position(|v| v == self).unwrap();
+                idx.to_string()
+            }
+
+            pub fn from_form_value(value: &str) -> Option<Self> {
+                let idx = value.parse::<usize>().ok()?;
+                [$($name::$variant,)+].get(idx).copied()
+            }
+        }
+    };
+}
+
+uconfig_enum!(
+    /// Loading the images through the Hentai@Home Network.
+    LoadFromHah {
+        AnyClient => LOAD_FROM_HAH_YES,
+        DefaultPortClient => LOAD_FROM_HAH_DEFAULT_PORT,
+        No => LOAD_FROM_HAH_NO,
+    }
+);
+
+uconfig_enum!(ImageSize {
+    Auto => IMAGE_SIZE_AUTO,
+    X780 => IMAGE_SIZE_780X,
+    X980 => IMAGE_SIZE_980X,
+    X1280 => IMAGE_SIZE_1280X,
+    X1600 => IMAGE_SIZE_1600X,
+    X2400 => IMAGE_SIZE_2400X,
+});
+
+uconfig_enum!(GalleryTitle {
+    Default => GALLERY_TITLE_DEFAULT,
+    Japanese => GALLERY_TITLE_JAPANESE,
+});
+
+uconfig_enum!(ArchiverDownload {
+    ManualAcceptManualStart => ARCHIVER_DOWNLOAD_MAMS,
+    ManualAcceptAutoStart => ARCHIVER_DOWNLOAD_AAMS,
+    AutoAcceptManualStart => ARCHIVER_DOWNLOAD_MAAS,
+    AutoAcceptAutoStart => ARCHIVER_DOWNLOAD_AAAS,
+});
+
+uconfig_enum!(
+    /// The display modes of [`crate::structures::gallery_list::GalleryList`].
+    LayoutMode {
+        Minimal => LAYOUT_MODE_MINIMAL,
+        MinimalPlus => LAYOUT_MODE_MINIMAL_PLUS,
+        Compact => LAYOUT_MODE_LIST,
+        Extended => LAYOUT_MODE_EXTENDED,
+        Thumbnail => LAYOUT_MODE_THUMB,
+    }
+);
+
+uconfig_enum!(FavoritesSort {
+    GalleryUpdateTime => FAVORITES_SORT_GALLERY_UPDATE_TIME,
+    FavoritedTime => FAVORITES_SORT_FAVORITED_TIME,
+});
+
+uconfig_enum!(
+    /// Requires `Hath Perk: Paging Enlargement` above 25.
+    ResultCount {
+        R25 => RESULT_COUNT_25,
+        R50 => RESULT_COUNT_50,
+        R100 => RESULT_COUNT_100,
+        R200 => RESULT_COUNT_200,
+    }
+);
+
+uconfig_enum!(MouseOver {
+    OnMouseOver => MOUSE_OVER_YES,
+    OnPageLoad => MOUSE_OVER_NO,
+});
+
+uconfig_enum!(PreviewSize {
+    Normal => PREVIEW_SIZE_NORMAL,
+    Large => PREVIEW_SIZE_LARGE,
+});
+
+uconfig_enum!(PreviewRow {
+    R4 => PREVIEW_ROW_4,
+    R10 => PREVIEW_ROW_10,
+    R20 => PREVIEW_ROW_20,
+    R40 => PREVIEW_ROW_40,
+});
+
+uconfig_enum!(CommentSort {
+    OldestFirst => COMMENTS_SORT_OLDEST_FIRST,
+    RecentFirst => COMMENTS_SORT_RECENT_FIRST,
+    HighestScoreFirst => COMMENTS_SORT_HIGHEST_SCORE_FIRST,
+});
+
+uconfig_enum!(CommentVotes {
+    /// On score hover or click.
+    Pop => COMMENTS_VOTES_POP,
+    Always => COMMENTS_VOTES_ALWAYS,
+});
+
+uconfig_enum!(TagSort {
+    Alphabetical => TAGS_SORT_ALPHABETICAL,
+    Power => TAGS_SORT_POWER,
+});
+
+uconfig_enum!(MultiPageStyle {
+    /// Align left, only scale if image is larger than browser width.
+    AlignLeft => MULTI_PAGE_STYLE_N,
+    /// Align center, only scale if image is larger than browser width.
+    AlignCenter => MULTI_PAGE_STYLE_C,
+    /// Align center, always scale images to fit browser width.
+    AlignCenterScale => MULTI_PAGE_STYLE_Y,
+});
+
+uconfig_enum!(MultiPageThumb {
+    Show => MULTI_PAGE_THUMB_SHOW,
+    Hide => MULTI_PAGE_THUMB_HIDE,
+});
+
+uconfig_enum!(
+    /// Lives in its own cookie, `xres`.
+    LofiResolution {
+        X460 => LOFI_RESOLUTION_460X,
+        X780 => LOFI_RESOLUTION_780X,
+        X980 => LOFI_RESOLUTION_980X,
+    }
+);
+
+/// The settings of an account, one value per account instead of the process wide ones.
+/// `to_string` gives the value of the `uconfig` cookie and `parse` reads it back.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UConfig {
+    pub load_from_hah: LoadFromHah,
+    pub image_size: ImageSize,
+    /// 0 for no limit.
+    pub scale_width: u32,
+    /// 0 for no limit.
+    pub scale_height: u32,
+    pub gallery_title: GalleryTitle,
+    pub archiver_download: ArchiverDownload,
+    pub layout_mode: LayoutMode,
+    pub popular: bool,
+    /// Mask of the categories hidden on the front page, 0 for none, see [`crate::structures::CategorySet::from_default_categories`].
+    pub default_categories: u32,
+    pub favorites_sort: FavoritesSort,
+    /// The namespaces excluded from a default tag search, the ones without a bit are dropped from the cookie,
+    /// see [`Namespace::mask_opt`].
+    pub excluded_namespace_vec: Vec<Namespace>,
+    pub excluded_language_vec: Vec<GalleryLanguage>,
+    pub result_count: ResultCount,
+    pub mouse_over: MouseOver,
+    pub preview_size: PreviewSize,
+    pub preview_row: PreviewRow,
+    pub comment_sort: CommentSort,
+    pub comment_votes: CommentVotes,
+    pub tag_sort: TagSort,
+    pub show_gallery_index: bool,
+    /// A proxy-enabled Hentai@Home Client to load all images.
+    pub hah_client_ip_opt: Option<String>,
+    pub hah_client_port_opt: Option<u16>,
+    pub hah_client_passkey_opt: Option<String>,
+    /// `Bronze Star` or `Hath Perk: Tag Flagging` Required.
+    pub enable_tag_flagging: bool,
+    /// `Silver Star` or `Hath Perk: Source Nexus` Required.
+    pub always_original: bool,
+    /// `Gold Star` or `Hath Perk: Multi-Page Viewer` Required.
+    pub multi_page: bool,
+    pub multi_page_style: MultiPageStyle,
+    pub multi_page_thumb: MultiPageThumb,
+    /// Lives in its own cookie, `xres`.
+    pub lofi_resolution: LofiResolution,
+    /// Lives in its own cookie, `nw`.
+    pub show_content_warning: bool,
+}
+
+impl Default for UConfig {
+    fn default() -> Self {
+        UConfig {
+            load_from_hah: LoadFromHah::AnyClient,
+            image_size: ImageSize::Auto,
+            scale_width: 0,
+            scale_height: 0,
+            gallery_title: GalleryTitle::Default,
+            archiver_download: ArchiverDownload::ManualAcceptManualStart,
+            layout_mode: LayoutMode::Compact,
+            popular: true,
+            default_categories: 0,
+            favorites_sort: FavoritesSort::FavoritedTime,
+            excluded_namespace_vec: Vec::new(),
+            excluded_language_vec: Vec::new(),
+            result_count: ResultCount::R25,
+            mouse_over: MouseOver::OnMouseOver,
+            preview_size: PreviewSize::Large,
+            preview_row: PreviewRow::R4,
+            comment_sort: CommentSort::OldestFirst,
+            comment_votes: CommentVotes::Pop,
+            tag_sort: TagSort::Alphabetical,
+            show_gallery_index: true,
+            hah_client_ip_opt: None,
+            hah_client_port_opt: None,
+            hah_client_passkey_opt: None,
+            enable_tag_flagging: false,
+            always_original: false,
+            multi_page: false,
+            multi_page_style: MultiPageStyle::AlignLeft,
+            multi_page_thumb: MultiPageThumb::Show,
+            lofi_resolution: LofiResolution::X980,
+            show_content_warning: false,
+        }
+    }
+}
+
+impl UConfig {
+    /// `uconfig`, `xres` and `nw`.
+    pub fn cookie_vec(&self) -> Vec<(String, String)> {
+        let content_warning = if self.show_content_warning { CONTENT_WARNING_SHOW } else { CONTENT_WARNING_NOT_SHOW };
+
+        vec![
+            (String::from(KEY_UCONFIG), self.to_string()),
+            (String::from(KEY_LOFI_RESOLUTION), String::from(self.lofi_resolution.value())),
+            (String::from(KEY_CONTENT_WARNING), String::from(content_warning)),
+        ]
+    }
+}
+
+impl Display for UConfig {
+    /// ```text
+    /// uh_y-xr_a-rx_0-ry_0-tl_r-ar_0-dm_l-prn_y-cats_0-fs_f-xns_0-xl_-rc_0-lt_m-ts_l-tr_2-cs_a-sc_0-tb_a-pn_1-hp_-hk_-tf_n-oi_n-qb_n-ms_n-mt_n
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let yes_no = |value: bool| if value { "y" } else { "n" };
+
+        let excluded_languages = self.excluded_language_vec.iter()
+            .map(|language| language.code().to_string())
+            .collect::<Vec<String>>()
+            .join(&UCONFIG_LANGUAGE_SEPARATOR.to_string());
+
+        let hah_client_ip_port = match (&self.hah_client_ip_opt, self.hah_client_port_opt) {
+            (Some(ip), Some(port)) => format!("{}%3A{}", ip, port),
+            _ => String::new(),
+        };
+
+        let show_gallery_index = if self.show_gallery_index { SHOW_GALLERY_INDEX_YES } else { SHOW_GALLERY_INDEX_NO };
+
+        let settings = [
+            (KEY_LOAD_FROM_HAH, self.load_from_hah.value().to_string()),
+            (KEY_IMAGE_SIZE, self.image_size.value().to_string()),
+            (KEY_SCALE_WIDTH, self.scale_width.to_string()),
+            (KEY_SCALE_HEIGHT, self.scale_height.to_string()),
+            (KEY_GALLERY_TITLE, self.gallery_title.value().to_string()),
+            (KEY_ARCHIVER_DOWNLOAD, self.archiver_download.value().to_string()),
+            (KEY_LAYOUT_MODE, self.layout_mode.value().to_string()),
+            (KEY_POPULAR, yes_no(self.popular).to_string()),
+            (KEY_DEFAULT_CATEGORIES, self.default_categories.to_string()),
+            (KEY_FAVORITES_SORT, self.favorites_sort.value().to_string()),
+            (KEY_EXCLUDED_NAMESPACES, Namespace::to_mask(&self.excluded_namespace_vec).to_string()),
+            (KEY_EXCLUDED_LANGUAGES, excluded_languages),
+            (KEY_RESULT_COUNT, self.result_count.value().to_string()),
+            (KEY_MOUSE_OVER, self.mouse_over.value().to_string()),
+            (KEY_PREVIEW_SIZE, self.preview_size.value().to_string()),
+            (KEY_PREVIEW_ROW, self.preview_row.value().to_string()),
+            (KEY_COMMENTS_SORT, self.comment_sort.value().to_string()),
+            (KEY_COMMENTS_VOTES, self.comment_votes.value().to_string()),
+            (KEY_TAGS_SORT, self.tag_sort.value().to_string()),
+            (KEY_SHOW_GALLERY_INDEX, show_gallery_index.to_string()),
+            (KEY_HAH_CLIENT_IP_PORT, hah_client_ip_port),
+            (KEY_HAH_CLIENT_PASSKEY, self.hah_client_passkey_opt.clone().unwrap_or_default()),
+            (KEY_ENABLE_TAG_FLAGGING, yes_no(self.enable_tag_flagging).to_string()),
+            (KEY_ALWAYS_ORIGINAL, yes_no(self.always_original).to_string()),
+            (KEY_MULTI_PAGE, yes_no(self.multi_page).to_string()),
+            (KEY_MULTI_PAGE_STYLE, self.multi_page_style.value().to_string()),
+            (KEY_MULTI_PAGE_THUMB, self.multi_page_thumb.value().to_string()),
+        ];
+
+        for (idx, (key, value)) in settings.iter().enumerate() {
+            if idx != 0 {
+                write!(f, "{}", UCONFIG_SETTING_SEPARATOR)?;
+            }
+            write!(f, "{}{}{}", key, UCONFIG_KEY_VALUE_SEPARATOR, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for UConfig {
+    type Err = ParseError;
+
+    /// Settings missing from the cookie keep their default value, unknown ones are skipped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = UConfig::default();
+
+        for setting in s.split(UCONFIG_SETTING_SEPARATOR) {
+            let Some((key, value)) = setting.split_once(UCONFIG_KEY_VALUE_SEPARATOR) else {
+                continue;
+            };
+
+            let invalid = || ParseError::from(format!("invalid uconfig setting: {}", setting));
+            let yes_no = || match value {
+                "y" => Ok(true),
+                "n" => Ok(false),
+                _ => Err(invalid()),
+            };
+
+            match key {
+                KEY_LOAD_FROM_HAH => config.load_from_hah = LoadFromHah::from_value(value).ok_or_else(invalid)?,
+                KEY_IMAGE_SIZE => config.image_size = ImageSize::from_value(value).ok_or_else(invalid)?,
+                KEY_SCALE_WIDTH => config.scale_width = value.parse()?,
+                KEY_SCALE_HEIGHT => config.scale_height = value.parse()?,
+                KEY_GALLERY_TITLE => config.gallery_title = GalleryTitle::from_value(value).ok_or_else(invalid)?,
+                KEY_ARCHIVER_DOWNLOAD => config.archiver_download = ArchiverDownload::from_value(value).ok_or_else(invalid)?,
+                KEY_LAYOUT_MODE => config.layout_mode = LayoutMode::from_value(value).ok_or_else(invalid)?,
+                KEY_POPULAR => config.popular = yes_no()?,
+                KEY_DEFAULT_CATEGORIES => config.default_categories = value.parse()?,
+                KEY_FAVORITES_SORT => config.favorites_sort = FavoritesSort::from_value(value).ok_or_else(invalid)?,
+                KEY_EXCLUDED_NAMESPACES => config.excluded_namespace_vec = Namespace::from_mask(value.parse()?),
+                KEY_EXCLUDED_LANGUAGES => {
+                    let mut excluded_language_vec = Vec::new();
+                    for code in value.split(UCONFIG_LANGUAGE_SEPARATOR).filter(|code| !code.is_empty()) {
+                        excluded_language_vec.push(GalleryLanguage::from_code(code.parse()?).ok_or_else(invalid)?);
+                    }
+                    config.excluded_language_vec = excluded_language_vec;
+                }
+                KEY_RESULT_COUNT => config.result_count = ResultCount::from_value(value).ok_or_else(invalid)?,
+                KEY_MOUSE_OVER => config.mouse_over = MouseOver::from_value(value).ok_or_else(invalid)?,
+                KEY_PREVIEW_SIZE => config.preview_size = PreviewSize::from_value(value).ok_or_else(invalid)?,
+                KEY_PREVIEW_ROW => config.preview_row = PreviewRow::from_value(value).ok_or_else(invalid)?,
+                KEY_COMMENTS_SORT => config.comment_sort = CommentSort::from_value(value).ok_or_else(invalid)?,
+                KEY_COMMENTS_VOTES => config.comment_votes = CommentVotes::from_value(value).ok_or_else(invalid)?,
+                KEY_TAGS_SORT => config.tag_sort = TagSort::from_value(value).ok_or_else(invalid)?,
+                KEY_SHOW_GALLERY_INDEX => config.show_gallery_index = value == SHOW_GALLERY_INDEX_YES,
+                KEY_HAH_CLIENT_IP_PORT => {
+                    if let Some((ip, port)) = value.split_once("%3A") {
+                        config.hah_client_ip_opt = Some(String::from(ip));
+                        config.hah_client_port_opt = Some(port.parse()?);
+                    }
+                }
+                KEY_HAH_CLIENT_PASSKEY => {
+                    config.hah_client_passkey_opt = Some(String::from(value)).filter(|passkey| !passkey.is_empty());
+                }
+                KEY_ENABLE_TAG_FLAGGING => config.enable_tag_flagging = yes_no()?,
+                KEY_ALWAYS_ORIGINAL => config.always_original = yes_no()?,
+                KEY_MULTI_PAGE => config.multi_page = yes_no()?,
+                KEY_MULTI_PAGE_STYLE => config.multi_page_style = MultiPageStyle::from_value(value).ok_or_else(invalid)?,
+                KEY_MULTI_PAGE_THUMB => config.multi_page_thumb = MultiPageThumb::from_value(value).ok_or_else(invalid)?,
+                _ => {}
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structures::{Language, Origin};
+    use super::*;
+
+    #[test]
+    fn default_test() {
+        let config = UConfig::default();
+        assert_eq!(
+            config.to_string(),
+            "uh_y-xr_a-rx_0-ry_0-tl_r-ar_0-dm_l-prn_y-cats_0-fs_f-xns_0-xl_-rc_0-lt_m-ts_l-tr_2-cs_a-sc_0-tb_a-pn_1-hp_-hk_-tf_n-oi_n-qb_n-ms_n-mt_n"
+        );
+        assert_eq!(config.cookie_vec()[2], (String::from("nw"), String::from("1")));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let config = UConfig {
+            load_from_hah: LoadFromHah::DefaultPortClient,
+            image_size: ImageSize::X1280,
+            layout_mode: LayoutMode::Extended,
+            default_categories: MISC | WESTERN,
+            excluded_namespace_vec: vec![Namespace::Parody, Namespace::Male],
+            excluded_language_vec: vec![
+                GalleryLanguage::new(Language::Japanese, Origin::Translated),
+                GalleryLanguage::new(Language::Japanese, Origin::Rewrite),
+                GalleryLanguage::new(Language::English, Origin::Original),
+            ],
+            result_count: ResultCount::R100,
+            hah_client_ip_opt: Some(String::from("192.168.1.2")),
+            hah_client_port_opt: Some(8080),
+            hah_client_passkey_opt: Some(String::from("abcdef")),
+            multi_page: true,
+            multi_page_style: MultiPageStyle::AlignCenterScale,
+            ..Default::default()
+        };
+
+        let cookie = config.to_string();
+        assert!(cookie.contains("-xns_68-xl_1024x2048x1-"));
+        assert!(cookie.contains("-hp_192.168.1.2%3A8080-hk_abcdef-"));
+        assert_eq!(cookie.parse::<UConfig>().unwrap(), config);
+
+        let config = "dm_t-xr_9999".parse::<UConfig>();
+        assert!(config.is_err());
+        assert!("xl_1024x999".parse::<UConfig>().is_err());
+
+        let config = "dm_t-unknown_1".parse::<UConfig>().unwrap();
+        assert_eq!(config.layout_mode, LayoutMode::Thumbnail);
+        assert_eq!(config.image_size, ImageSize::Auto);
+    }
+}
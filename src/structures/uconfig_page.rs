@@ -0,0 +1,365 @@
+use visdom::{types::Elements, Vis};
+use crate::{eh_config::*, EhResult, ParseError, Parser, structures::{GalleryLanguage, Namespace, uconfig::*}};
+
+/// The settings page, uconfig.php.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UConfigPage {
+    pub config: UConfig,
+    /// Names of the favorite slots, from 0 to 9.
+    pub favorite_name_vec: Vec<String>,
+    /// Thumbnail scaling in percent, 100 by default.
+    pub thumbnail_scale_opt: Option<u32>,
+    /// Virtual width of the viewport on mobile devices, `None` when not overridden.
+    pub viewport_width_opt: Option<u32>,
+    pub profile_vec: Vec<UConfigProfile>,
+    /// The fields of the form not covered by the other members, posted back as they are.
+    pub other_field_vec: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UConfigProfile {
+    pub id: u32,
+    pub name: String,
+    /// The profile the page shows and [`UConfigPage::form_vec`] applies to.
+    pub selected: bool,
+}
+
+impl UConfigPage {
+    /// The form posted to uconfig.php to apply [`UConfigPage::config`] to the selected profile.
+    pub fn form_vec(&self) -> Vec<(String, String)> {
+        let config = &self.config;
+        let pair = |key: &str, value: String| (String::from(key), value);
+        let yes_no = |value: bool| String::from(if value { "1" } else { "0" });
+
+        let hah_client = match (&config.hah_client_ip_opt, config.hah_client_port_opt) {
+            (Some(ip), Some(port)) => format!("{}:{}", ip, port),
+            _ => String::new(),
+        };
+
+        let mut form_vec = vec![
+            pair(KEY_LOAD_FROM_HAH, config.load_from_hah.form_value()),
+            pair(KEY_IMAGE_SIZE, config.image_size.form_value()),
+            pair(KEY_SCALE_WIDTH, number_or_empty(config.scale_width)),
+            pair(KEY_SCALE_HEIGHT, number_or_empty(config.scale_height)),
+            pair(KEY_GALLERY_TITLE, config.gallery_title.form_value()),
+            pair(KEY_ARCHIVER_DOWNLOAD, config.archiver_download.form_value()),
+            pair(KEY_LAYOUT_MODE, config.layout_mode.form_value()),
+            pair(KEY_POPULAR, yes_no(config.popular)),
+        ];
+
+        for (name, category) in FORM_CATEGORIES {
+            if config.default_categories & category != 0 {
+                form_vec.push(pair(name, String::from("on")));
+            }
+        }
+
+        for (slot, name) in self.favorite_name_vec.iter().enumerate() {
+            form_vec.push(pair(&format!("{}{}", FORM_FAVORITE_PREFIX, slot), name.clone()));
+        }
+        form_vec.push(pair(KEY_FAVORITES_SORT, config.favorites_sort.form_value()));
+
+        let excluded_namespaces = Namespace::to_mask(&config.excluded_namespace_vec);
+        for bit in 0..FORM_NAMESPACE_COUNT {
+            if excluded_namespaces & (1 << bit) != 0 {
+                form_vec.push(pair(&format!("{}{}", FORM_NAMESPACE_PREFIX, bit + 1), String::from("on")));
+            }
+        }
+
+        for language in &config.excluded_language_vec {
+            form_vec.push(pair(&format!("{}{}", FORM_LANGUAGE_PREFIX, language.code()), String::from("on")));
+        }
+
+        form_vec.extend([
+            pair(KEY_RESULT_COUNT, config.result_count.form_value()),
+            pair(KEY_MOUSE_OVER, config.mouse_over.form_value()),
+            pair(KEY_PREVIEW_SIZE, config.preview_size.form_value()),
+            pair(KEY_PREVIEW_ROW, config.preview_row.form_value()),
+            pair(FORM_THUMBNAIL_SCALE, self.thumbnail_scale_opt.map(|scale| scale.to_string()).unwrap_or_default()),
+            pair(FORM_VIEWPORT_WIDTH, self.viewport_width_opt.map(|width| width.to_string()).unwrap_or_default()),
+            pair(KEY_COMMENTS_SORT, config.comment_sort.form_value()),
+            pair(KEY_COMMENTS_VOTES, config.comment_votes.form_value()),
+            pair(KEY_TAGS_SORT, config.tag_sort.form_value()),
+            pair(KEY_SHOW_GALLERY_INDEX, yes_no(config.show_gallery_index)),
+            pair(KEY_HAH_CLIENT_IP_PORT, hah_client),
+            pair(KEY_HAH_CLIENT_PASSKEY, config.hah_client_passkey_opt.clone().unwrap_or_default()),
+            pair(KEY_ENABLE_TAG_FLAGGING, yes_no(config.enable_tag_flagging)),
+            pair(KEY_ALWAYS_ORIGINAL, yes_no(config.always_original)),
+            pair(KEY_MULTI_PAGE, yes_no(config.multi_page)),
+            pair(KEY_MULTI_PAGE_STYLE, config.multi_page_style.form_value()),
+            pair(KEY_MULTI_PAGE_THUMB, config.multi_page_thumb.form_value()),
+        ]);
+
+        form_vec.extend(self.other_field_vec.iter().cloned());
+        form_vec.push(pair("apply", String::from("Apply")));
+        form_vec
+    }
+}
+
+impl Parser for UConfigPage {
+    /// Reads the state of the settings form, the settings the page does not show keep their default value.
+    /// ```html
+    /// <input type="radio" name="xr" id="xr_3" value="3" checked="checked" />
+    /// <input type="checkbox" name="ct_misc" id="ct_misc" checked="checked" />
+    /// <input type="checkbox" name="xl_1024" id="xl_1024" checked="checked" />
+    /// <input type="text" name="hp" value="192.168.1.2:8080" />
+    /// <select name="profile_set"><option value="1" selected="selected">Default Profile</option></select>
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let root = Vis::load(doc)?;
+        let field_vec = form_fields(&root.find("form input, form select, form textarea"));
+        if field_vec.is_empty() {
+            return Err(ParseError::from(String::from("uconfig form not found")));
+        }
+
+        let mut config = UConfig::default();
+        let mut favorite_name_vec = Vec::new();
+        let mut thumbnail_scale_opt = None;
+        let mut viewport_width_opt = None;
+        let mut other_field_vec = Vec::new();
+
+        for (name, value) in field_vec {
+            let invalid = || ParseError::from(format!("invalid uconfig field: {}={}", name, value));
+            let value = value.as_str();
+
+            match name.as_str() {
+                KEY_LOAD_FROM_HAH => config.load_from_hah = LoadFromHah::from_form_value(value).ok_or_else(invalid)?,
+                KEY_IMAGE_SIZE => config.image_size = ImageSize::from_form_value(value).ok_or_else(invalid)?,
+                KEY_SCALE_WIDTH => config.scale_width = number_opt(value)?.unwrap_or(0),
+                KEY_SCALE_HEIGHT => config.scale_height = number_opt(value)?.unwrap_or(0),
+                KEY_GALLERY_TITLE => config.gallery_title = GalleryTitle::from_form_value(value).ok_or_else(invalid)?,
+                KEY_ARCHIVER_DOWNLOAD => config.archiver_download = ArchiverDownload::from_form_value(value).ok_or_else(invalid)?,
+                KEY_LAYOUT_MODE => config.layout_mode = LayoutMode::from_form_value(value).ok_or_else(invalid)?,
+                KEY_POPULAR => config.popular = value == "1",
+                KEY_FAVORITES_SORT => config.favorites_sort = FavoritesSort::from_form_value(value).ok_or_else(invalid)?,
+                KEY_RESULT_COUNT => config.result_count = ResultCount::from_form_value(value).ok_or_else(invalid)?,
+                KEY_MOUSE_OVER => config.mouse_over = MouseOver::from_form_value(value).ok_or_else(invalid)?,
+                KEY_PREVIEW_SIZE => config.preview_size = PreviewSize::from_form_value(value).ok_or_else(invalid)?,
+                KEY_PREVIEW_ROW => config.preview_row = PreviewRow::from_form_value(value).ok_or_else(invalid)?,
+                FORM_THUMBNAIL_SCALE => thumbnail_scale_opt = number_opt(value)?,
+                FORM_VIEWPORT_WIDTH => viewport_width_opt = number_opt(value)?,
+                KEY_COMMENTS_SORT => config.comment_sort = CommentSort::from_form_value(value).ok_or_else(invalid)?,
+                KEY_COMMENTS_VOTES => config.comment_votes = CommentVotes::from_form_value(value).ok_or_else(invalid)?,
+                KEY_TAGS_SORT => config.tag_sort = TagSort::from_form_value(value).ok_or_else(invalid)?,
+                KEY_SHOW_GALLERY_INDEX => config.show_gallery_index = value == "1",
+                KEY_HAH_CLIENT_IP_PORT => {
+                    if let Some((ip, port)) = value.split_once(':') {
+                        config.hah_client_ip_opt = Some(String::from(ip));
+                        config.hah_client_port_opt = Some(port.parse()?);
+                    }
+                }
+                KEY_HAH_CLIENT_PASSKEY => {
+                    config.hah_client_passkey_opt = Some(String::from(value)).filter(|passkey| !passkey.is_empty());
+                }
+                KEY_ENABLE_TAG_FLAGGING => config.enable_tag_flagging = value == "1",
+                KEY_ALWAYS_ORIGINAL => config.always_original = value == "1",
+                KEY_MULTI_PAGE => config.multi_page = value == "1",
+                KEY_MULTI_PAGE_STYLE => config.multi_page_style = MultiPageStyle::from_form_value(value).ok_or_else(invalid)?,
+                KEY_MULTI_PAGE_THUMB => config.multi_page_thumb = MultiPageThumb::from_form_value(value).ok_or_else(invalid)?,
+                "apply" => {}
+                name => {
+                    if let Some((_, category)) = FORM_CATEGORIES.iter().find(|(key, _)| *key == name) {
+                        config.default_categories |= category;
+                    } else if let Some(bit) = namespace_bit_opt(name) {
+                        config.excluded_namespace_vec.extend(Namespace::from_mask(1 << bit));
+                    } else if let Some(code) = name.strip_prefix(FORM_LANGUAGE_PREFIX) {
+                        config.excluded_language_vec.push(GalleryLanguage::from_code(code.parse()?).ok_or_else(invalid)?);
+                    } else if name.starts_with(FORM_FAVORITE_PREFIX) {
+                        favorite_name_vec.push(String::from(value));
+                    } else {
+                        other_field_vec.push((String::from(name), String::from(value)));
+                    }
+                }
+            }
+        }
+
+        let profile_vec = root.find("select[name=profile_set] option").map(|_, ele| {
+            let id = ele.get_attribute("value").map(|value| value.to_string()).unwrap_or_default();
+            UConfigProfile {
+                id: id.parse().unwrap_or(0),
+                name: ele.text().trim().to_string(),
+                selected: ele.has_attribute("selected"),
+            }
+        });
+
+        Ok(UConfigPage {
+            config,
+            favorite_name_vec,
+            thumbnail_scale_opt,
+            viewport_width_opt,
+            profile_vec,
+            other_field_vec,
+        })
+    }
+}
+
+/// What the browser would post: the checked radios and checkboxes, the texts and the selected options.
+fn form_fields(elements: &Elements) -> Vec<(String, String)> {
+    let field_vec = elements.map(|_, ele| {
+        let name = ele.get_attribute("name")?.to_string();
+        let kind = ele.get_attribute("type").map(|kind| kind.to_string()).unwrap_or_default();
+
+        match kind.as_str() {
+            "radio" | "checkbox" => {
+                if !ele.has_attribute("checked") {
+                    return None;
+                }
+                let value = ele.get_attribute("value").map(|value| value.to_string());
+                Some((name, value.unwrap_or_else(|| String::from("on"))))
+            }
+            "submit" | "button" => None,
+            _ => Some((name, ele.value().to_string())),
+        }
+    });
+
+    field_vec.into_iter().flatten().collect()
+}
+
+/// The bit of an `xn_` field, `None` for a checkbox the site added since, posted back as it is.
+fn namespace_bit_opt(name: &str) -> Option<u32> {
+    let number = name.strip_prefix(FORM_NAMESPACE_PREFIX)?.parse::<u32>().ok()?;
+    number.checked_sub(1).filter(|bit| *bit < FORM_NAMESPACE_COUNT)
+}
+
+fn number_opt(value: &str) -> EhResult<Option<u32>> {
+    let value = value.trim();
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(value.parse()?))
+    }
+}
+
+fn number_or_empty(value: u32) -> String {
+    if value == 0 { String::new() } else { value.to_string() }
+}
+
+const FORM_CATEGORIES: [(&str, u32); 10] = [
+    ("ct_misc", MISC),
+    ("ct_doujinshi", DOUJINSHI),
+    ("ct_manga", MANGA),
+    ("ct_artistcg", ARTIST_CG),
+    ("ct_gamecg", GAME_CG),
+    ("ct_imageset", IMAGE_SET),
+    ("ct_cosplay", COSPLAY),
+    ("ct_asianporn", ASIAN_PORN),
+    ("ct_nonh", NON_H),
+    ("ct_western", WESTERN),
+];
+/// `xn_1` for `NAMESPACES_RECLASS` up to `xn_8` for `NAMESPACES_FEMALE`.
+const FORM_NAMESPACE_PREFIX: &str = "xn_";
+const FORM_NAMESPACE_COUNT: u32 = 8;
+const FORM_LANGUAGE_PREFIX: &str = "xl_";
+const FORM_FAVORITE_PREFIX: &str = "favorite_";
+const FORM_THUMBNAIL_SCALE: &str = "tp";
+const FORM_VIEWPORT_WIDTH: &str = "vp";
+
+#[cfg(test)]
+mod tests {
+    use crate::structures::{Language, Origin};
+    use super::*;
+
+    const DOC: &str = r#"
+<form method="post" action="">
+<input type="radio" name="uh" value="0" /><input type="radio" name="uh" value="2" checked="checked" />
+<input type="radio" name="xr" value="0" /><input type="radio" name="xr" value="3" checked="checked" />
+<input type="text" name="rx" value="" /><input type="text" name="ry" value="1200" />
+<input type="radio" name="dm" value="2" /><input type="radio" name="dm" value="3" checked="checked" />
+<input type="radio" name="prn" value="1" /><input type="radio" name="prn" value="0" checked="checked" />
+<input type="checkbox" name="ct_misc" checked="checked" /><input type="checkbox" name="ct_manga" />
+<input type="checkbox" name="ct_western" checked="checked" />
+<input type="text" name="favorite_0" value="Favorites 0" /><input type="text" name="favorite_1" value="To read" />
+<input type="radio" name="fs" value="0" checked="checked" />
+<input type="checkbox" name="xn_3" checked="checked" /><input type="checkbox" name="xn_7" checked="checked" />
+<input type="checkbox" name="xl_1024" checked="checked" /><input type="checkbox" name="xl_2048" checked="checked" /><input type="checkbox" name="xl_10" />
+<input type="text" name="ru" value="RRGGB" />
+<input type="radio" name="rc" value="2" checked="checked" />
+<input type="radio" name="tr" value="1" checked="checked" />
+<input type="text" name="tp" value="150" /><input type="text" name="vp" value="" />
+<input type="radio" name="cs" value="2" checked="checked" />
+<textarea name="xu">uploader1
+uploader2</textarea>
+<input type="text" name="hp" value="192.168.1.2:8080" /><input type="text" name="hk" value="abcdef" />
+<input type="radio" name="tf" value="0" /><input type="radio" name="tf" value="1" checked="checked" />
+<input type="radio" name="qb" value="1" checked="checked" />
+<input type="radio" name="ms" value="2" checked="checked" />
+<select name="profile_set"><option value="1">Default Profile</option><option value="2" selected="selected">Mobile</option></select>
+<input type="submit" name="apply" value="Apply" />
+</form>
+"#;
+
+    #[test]
+    fn parse_test() {
+        let page = UConfigPage::parse(DOC).unwrap();
+        assert_eq!(page.config, UConfig {
+            load_from_hah: LoadFromHah::No,
+            image_size: ImageSize::X1280,
+            scale_height: 1200,
+            layout_mode: LayoutMode::Extended,
+            popular: false,
+            default_categories: MISC | WESTERN,
+            favorites_sort: FavoritesSort::GalleryUpdateTime,
+            excluded_namespace_vec: vec![Namespace::Parody, Namespace::Male],
+            excluded_language_vec: vec![
+                GalleryLanguage::new(Language::Japanese, Origin::Translated),
+                GalleryLanguage::new(Language::Japanese, Origin::Rewrite),
+            ],
+            result_count: ResultCount::R100,
+            preview_row: PreviewRow::R10,
+            comment_sort: CommentSort::HighestScoreFirst,
+            hah_client_ip_opt: Some(String::from("192.168.1.2")),
+            hah_client_port_opt: Some(8080),
+            hah_client_passkey_opt: Some(String::from("abcdef")),
+            enable_tag_flagging: true,
+            multi_page: true,
+            multi_page_style: MultiPageStyle::AlignCenterScale,
+            ..Default::default()
+        });
+        assert_eq!(page.favorite_name_vec, vec![String::from("Favorites 0"), String::from("To read")]);
+        assert_eq!(page.thumbnail_scale_opt, Some(150));
+        assert_eq!(page.viewport_width_opt, None);
+        assert_eq!(page.profile_vec[1], UConfigProfile { id: 2, name: String::from("Mobile"), selected: true });
+        assert_eq!(page.other_field_vec, vec![
+            (String::from("ru"), String::from("RRGGB")),
+            (String::from("xu"), String::from("uploader1\nuploader2")),
+            (String::from("profile_set"), String::from("2")),
+        ]);
+
+        assert!(UConfigPage::parse("<html><body></body></html>").is_err());
+
+        let page = UConfigPage::parse(&DOC.replace(r#"name="xn_3""#, r#"name="xn_9""#)).unwrap();
+        assert_eq!(page.config.excluded_namespace_vec, vec![Namespace::Male]);
+        assert_eq!(page.other_field_vec[0], (String::from("xn_9"), String::from("on")));
+        assert!(page.form_vec().contains(&(String::from("xn_9"), String::from("on"))));
+        let page = UConfigPage::parse(&DOC.replace(r#"name="xn_3""#, r#"name="xn_0""#)).unwrap();
+        assert_eq!(page.other_field_vec[0], (String::from("xn_0"), String::from("on")));
+    }
+
+    #[test]
+    fn form_vec_test() {
+        let mut page = UConfigPage::parse(DOC).unwrap();
+        page.config.layout_mode = LayoutMode::Thumbnail;
+        page.config.popular = true;
+        page.config.excluded_language_vec.push(GalleryLanguage::new(Language::Chinese, Origin::Original));
+
+        let form_vec = page.form_vec();
+        let value_opt = |key: &str| form_vec.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+        assert_eq!(value_opt("uh"), Some("2"));
+        assert_eq!(value_opt("dm"), Some("4"));
+        assert_eq!(value_opt("rx"), Some(""));
+        assert_eq!(value_opt("prn"), Some("1"));
+        assert_eq!(value_opt("tf"), Some("1"));
+        assert_eq!(value_opt("xl_10"), Some("on"));
+        assert_eq!(value_opt("xn_7"), Some("on"));
+        assert_eq!(value_opt("ct_manga"), None);
+        assert_eq!(value_opt("hp"), Some("192.168.1.2:8080"));
+        assert_eq!(value_opt("ru"), Some("RRGGB"));
+        assert_eq!(form_vec.last(), Some(&(String::from("apply"), String::from("Apply"))));
+
+        let doc = DOC.replace(r#"<input type="radio" name="uh" value="2" checked="checked" />"#, r#"<input type="radio" name="uh" value="1" checked="checked" />"#);
+        let page = UConfigPage::parse(&doc).unwrap();
+        assert_eq!(page.config.load_from_hah, LoadFromHah::DefaultPortClient);
+        assert!(page.form_vec().contains(&(String::from("uh"), String::from("1"))));
+    }
+}
@@ -0,0 +1,113 @@
+use regex::Regex;
+use visdom::Vis;
+use crate::{DOM_NOT_FOUND, EhResult, Parser, REGEX_MATCH_FAILED};
+
+/// A watched or hidden tag of a tag set on the My Tags page.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserTag {
+    /// 0 for a tag not added yet.
+    pub id: u64,
+    /// `namespace:tag`
+    pub tag: String,
+    pub watched: bool,
+    pub hidden: bool,
+    /// Like `#ff0000`, `None` for the color of the tag set.
+    pub color_opt: Option<String>,
+    /// From -99 to 99, 10 by default.
+    pub weight: i32,
+}
+
+impl UserTag {
+    /// A watched tag with the default weight, ready for [`crate::request::add_user_tag`].
+    pub fn new(tag: &str) -> Self {
+        UserTag {
+            id: 0,
+            tag: String::from(tag),
+            watched: true,
+            hidden: false,
+            color_opt: None,
+            weight: DEFAULT_WEIGHT,
+        }
+    }
+}
+
+impl Parser for UserTag {
+    /// ```html
+    /// <div id="usertag_12345">
+    ///     <div><div id="tagpreview_12345" class="gt" title="female:big breasts">female:big breasts</div></div>
+    ///     <div><input type="checkbox" name="modify_usertags[]" value="12345" /></div>
+    ///     <div><input type="checkbox" id="tagwatch_12345" checked="checked" /></div>
+    ///     <div><input type="checkbox" id="taghide_12345" /></div>
+    ///     <div><input type="text" id="tagcolor_12345" value="#ff0000" /></div>
+    ///     <div><input type="text" id="tagweight_12345" value="10" /></div>
+    /// </div>
+    /// ```
+    fn parse(doc: &str) -> EhResult<Self> {
+        let root = Vis::load(doc)?;
+        let user_tag = root.find("[id^=usertag_]");
+        let id = user_tag.attr("id").ok_or(DOM_NOT_FOUND)?.to_string();
+        let regex = Regex::new(PATTERN_ID).unwrap();
+        let captures = regex.captures(&id).ok_or(REGEX_MATCH_FAILED)?;
+        let id: u64 = captures[1].parse()?;
+
+        let preview = root.find(&format!("#tagpreview_{}", id));
+        if preview.is_empty() {
+            return Err(DOM_NOT_FOUND);
+        }
+        let tag = match preview.attr("title") {
+            Some(title) => title.to_string(),
+            None => preview.text().trim().to_string(),
+        };
+
+        let watched = root.find(&format!("#tagwatch_{}", id)).has_attr("checked");
+        let hidden = root.find(&format!("#taghide_{}", id)).has_attr("checked");
+
+        let color = root.find(&format!("#tagcolor_{}", id)).val().to_string();
+        let color_opt = Some(color).filter(|color| !color.is_empty());
+
+        let weight = root.find(&format!("#tagweight_{}", id)).val().to_string();
+        let weight = if weight.is_empty() { DEFAULT_WEIGHT } else { weight.parse()? };
+
+        Ok(UserTag {
+            id,
+            tag,
+            watched,
+            hidden,
+            color_opt,
+            weight,
+        })
+    }
+}
+
+const DEFAULT_WEIGHT: i32 = 10;
+const PATTERN_ID: &str = r#"^usertag_(\d+)$"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let doc = r##"
+<div id="usertag_12345" style="height:29px">
+    <div style="float:left"><div id="tagpreview_12345" class="gt" title="female:big breasts" style="color:#f1f1f1">female:big breasts</div></div>
+    <div style="float:left"><input type="checkbox" name="modify_usertags[]" value="12345" /></div>
+    <div style="float:left"><input type="checkbox" id="tagwatch_12345" checked="checked" /></div>
+    <div style="float:left"><input type="checkbox" id="taghide_12345" /></div>
+    <div style="float:left"><input type="text" id="tagcolor_12345" value="#ff0000" /></div>
+    <div style="float:left"><input type="text" id="tagweight_12345" value="-5" /></div>
+</div>
+"##;
+        assert_eq!(UserTag::parse(doc).unwrap(), UserTag {
+            id: 12345,
+            tag: String::from("female:big breasts"),
+            watched: true,
+            hidden: false,
+            color_opt: Some(String::from("#ff0000")),
+            weight: -5,
+        });
+
+        assert!(UserTag::parse(r#"<div id="usertag_0"><input id="tagname_new" /></div>"#).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+
+/// Percent-encodes everything but the unreserved characters, space becomes `+`.
+pub fn encode(str: &str) -> String {
+    let mut encoded = String::with_capacity(str.len());
+    for byte in str.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+/// The reverse of [`encode`], malformed escapes are kept as they are.
+pub fn decode(str: &str) -> Cow<'_, str> {
+    if !str.contains(['%', '+']) {
+        return Cow::Borrowed(str);
+    }
+
+    let bytes = str.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex_opt = str.get(idx + 1..idx + 3)
+                    .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()));
+                match hex_opt {
+                    Some(hex) => {
+                        decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                        idx += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        idx += 1;
+    }
+
+    Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// `key=value&key=value` of a query string or a form body.
+pub fn decode_pairs(str: &str) -> Vec<(String, String)> {
+    str.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode(key).into_owned(), decode(value).into_owned()),
+            None => (decode(pair).into_owned(), String::new()),
+        })
+        .collect()
+}
+
+/// The reverse of [`decode_pairs`].
+pub fn encode_pairs(pairs: &[(String, String)]) -> String {
+    pairs.iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_test() {
+        let str = r#"female:"big breasts$" 漢化"#;
+        assert_eq!(encode(str), "female%3A%22big+breasts%24%22+%E6%BC%A2%E5%8C%96");
+        assert_eq!(decode(&encode(str)), str);
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz"), "%zz");
+    }
+}